@@ -0,0 +1,56 @@
+use core::error::Error;
+
+/// An error, or an error trait object, which can be borrowed as a trait
+/// object.
+pub(crate) trait AsDynError {
+    /// Borrows the error as a trait object.
+    fn as_dyn_error(&self) -> &(dyn Error + '_);
+
+    /// Borrows the error as a `'static` trait object, which can be
+    /// downcasted.
+    fn as_static_error(&self) -> &(dyn Error + 'static)
+    where
+        Self: 'static;
+}
+
+impl<E> AsDynError for E
+where
+    E: Error,
+{
+    fn as_dyn_error(&self) -> &(dyn Error + '_) {
+        self
+    }
+
+    fn as_static_error(&self) -> &(dyn Error + 'static)
+    where
+        Self: 'static,
+    {
+        self
+    }
+}
+
+/// Implements [`AsDynError`] for the error trait objects.
+macro_rules! impl_trait_objects {
+    ($($object:ty),* $(,)?) => {
+        $(
+            impl<'a> AsDynError for $object {
+                fn as_dyn_error(&self) -> &(dyn Error + '_) {
+                    self
+                }
+
+                fn as_static_error(&self) -> &(dyn Error + 'static)
+                where
+                    Self: 'static,
+                {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_trait_objects!(
+    dyn Error + 'a,
+    dyn Error + Send + 'a,
+    dyn Error + Send + Sync + 'a,
+);
@@ -0,0 +1,242 @@
+use core::{error::Error, iter, iter::FusedIterator, ptr};
+
+/// An iterator over an error and its sources.
+///
+/// The iteration starts with the error itself and continues with its
+/// [sources][Error::source]. It stops at the first error which has already
+/// been produced, so a faulty [`Error::source`] implementation can't make it
+/// loop forever.
+///
+/// ```rust
+/// use display_error_chain::ErrorChainExt as _;
+///
+/// # #[derive(Debug)]
+/// # struct ConfigError(std::io::Error);
+/// # impl std::fmt::Display for ConfigError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         write!(f, "failed to read the config")
+/// #     }
+/// # }
+/// # impl std::error::Error for ConfigError {
+/// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+/// #         Some(&self.0)
+/// #     }
+/// # }
+/// let error = ConfigError(std::io::Error::new(
+///     std::io::ErrorKind::NotFound,
+///     "no such file",
+/// ));
+///
+/// let messages: Vec<_> = error.sources().map(|error| error.to_string()).collect();
+/// assert_eq!(messages, ["failed to read the config", "no such file"]);
+///
+/// let root_first: Vec<_> = error.sources().rev().map(|error| error.to_string()).collect();
+/// assert_eq!(root_first, ["no such file", "failed to read the config"]);
+/// ```
+///
+/// The length of the chain is calculated upfront, and the chain is walked
+/// anew on every [`next_back`][DoubleEndedIterator::next_back] call, so the
+/// iterator never allocates. As a consequence, iterating in the reverse order
+/// (e.g. with [`rev`][Iterator::rev]) takes quadratic time in the length of
+/// the chain. For very deep chains, consider collecting the errors into a
+/// vector first.
+#[derive(Debug, Clone)]
+pub struct Chain<'a> {
+    next: Option<&'a (dyn Error + 'a)>,
+    len: usize,
+    cyclic: bool,
+}
+
+impl<'a> Chain<'a> {
+    /// Starts iterating from the given error.
+    pub fn new(error: &'a (dyn Error + 'a)) -> Self {
+        let (len, cyclic) = measure(error);
+        Chain {
+            next: Some(error),
+            len,
+            cyclic,
+        }
+    }
+
+    /// An iterator which produces nothing.
+    pub(crate) fn empty() -> Self {
+        Chain {
+            next: None,
+            len: 0,
+            cyclic: false,
+        }
+    }
+
+    /// Checks whether the chain was cut because of a cycle.
+    pub(crate) fn is_cyclic(&self) -> bool {
+        self.cyclic
+    }
+}
+
+/// Iterates over the same errors as [`Chain`] does, but keeps them `'static`,
+/// so they can be downcasted.
+///
+/// [`Chain`] can't produce `'static` errors, since the top-level error is not
+/// required to be `'static`.
+pub(crate) fn static_sources<'a>(
+    error: &'a (dyn Error + 'static),
+) -> impl Iterator<Item = &'a (dyn Error + 'static)> {
+    iter::successors(Some(error), |&error| error.source()).take(Chain::new(error).len())
+}
+
+/// Calculates the number of distinct errors of the chain, and checks whether
+/// the chain is cut because of a cycle.
+///
+/// Brent's cycle detection algorithm is used, so the chain is walked a
+/// constant number of times and nothing is allocated.
+fn measure<'a>(error: &'a (dyn Error + 'a)) -> (usize, bool) {
+    let mut power = 1;
+    let mut cycle_len = 1;
+    let mut len = 1;
+    let mut tortoise = error;
+    let mut hare = error.source();
+    loop {
+        let Some(current) = hare else {
+            return (len, false);
+        };
+        if ptr::eq(tortoise, current) {
+            break;
+        }
+        if power == cycle_len {
+            tortoise = current;
+            power *= 2;
+            cycle_len = 0;
+        }
+        hare = current.source();
+        cycle_len += 1;
+        len += 1;
+    }
+
+    // The cycle starts at the first error which is the same as the one
+    // `cycle_len` errors further down the chain.
+    let successors = || iter::successors(Some(error), |&error| error.source());
+    let cycle_start = successors()
+        .zip(successors().skip(cycle_len))
+        .take_while(|&(error, further)| !ptr::eq(error, further))
+        .count();
+    (cycle_start + cycle_len, true)
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'a);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.len = self.len.checked_sub(1)?;
+        let error = self.next?;
+        self.next = error.source();
+        Some(error)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn count(self) -> usize {
+        self.len
+    }
+}
+
+impl DoubleEndedIterator for Chain<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.len = self.len.checked_sub(1)?;
+        iter::successors(self.next, |&error| error.source()).nth(self.len)
+    }
+}
+
+impl ExactSizeIterator for Chain<'_> {}
+
+impl FusedIterator for Chain<'_> {}
+
+#[cfg(test)]
+mod test {
+    use super::Chain;
+    use std::{error::Error, fmt};
+
+    #[derive(Debug)]
+    struct Cyclic;
+
+    impl fmt::Display for Cyclic {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "cyclic")
+        }
+    }
+
+    impl Error for Cyclic {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(self)
+        }
+    }
+
+    /// An error of a ring of errors, each being the source of the previous
+    /// one.
+    #[derive(Debug)]
+    struct Ring(usize);
+
+    static RING: [Ring; 3] = [Ring(0), Ring(1), Ring(2)];
+
+    impl fmt::Display for Ring {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "ring {}", self.0)
+        }
+    }
+
+    impl Error for Ring {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&RING[(self.0 + 1) % RING.len()])
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        let mut chain = Chain::new(&Cyclic);
+        assert!(chain.is_cyclic());
+        assert_eq!(chain.len(), 1);
+        assert!(chain.next_back().is_some());
+        assert!(chain.next().is_none());
+        assert!(chain.next_back().is_none());
+    }
+
+    /// An error which shares its address with its source.
+    #[derive(Debug)]
+    struct Transparent(std::io::Error);
+
+    impl fmt::Display for Transparent {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl Error for Transparent {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn shared_addresses() {
+        let error = Transparent(std::io::Error::other("oops"));
+        let chain = Chain::new(&error);
+        assert!(!chain.is_cyclic());
+        assert_eq!(chain.len(), 2);
+    }
+
+    #[test]
+    fn long_cycles() {
+        let chain = Chain::new(&RING[0]);
+        assert!(chain.is_cyclic());
+        assert_eq!(chain.len(), 3);
+
+        // A copy of a ring error is not a part of the ring, so it leads into
+        // the cycle.
+        let chain = Chain::new(&Ring(1));
+        assert!(chain.is_cyclic());
+        assert_eq!(chain.len(), 4);
+        let messages: Vec<_> = chain.map(|error| error.to_string()).collect();
+        assert_eq!(messages, ["ring 1", "ring 2", "ring 0", "ring 1"]);
+    }
+}
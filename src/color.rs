@@ -0,0 +1,156 @@
+use core::fmt;
+#[cfg(feature = "color")]
+use std::ffi::OsStr;
+
+use crate::Style;
+
+/// Whether the output of a [`DisplayErrorChain`][crate::DisplayErrorChain]
+/// is colored with ANSI escape codes.
+#[cfg(feature = "color")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum ColorChoice {
+    /// Colors are enabled unless the `NO_COLOR` environment variable is set
+    /// to a non-empty value. The `CLICOLOR_FORCE` environment variable
+    /// (unless it's set to `0`) takes precedence and enables colors anyway.
+    ///
+    /// Please note that whether the output is a terminal is not checked,
+    /// since the formatter has no idea where the output goes.
+    Auto,
+    /// Colors are always enabled.
+    Always,
+    /// Colors are always disabled.
+    #[default]
+    Never,
+}
+
+#[cfg(feature = "color")]
+impl ColorChoice {
+    /// Checks whether the colors are enabled.
+    fn is_enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => auto_colors(
+                std::env::var_os("CLICOLOR_FORCE").as_deref(),
+                std::env::var_os("NO_COLOR").as_deref(),
+            ),
+        }
+    }
+}
+
+/// Decides whether the colors are enabled in the [automatic][ColorChoice::Auto]
+/// mode, given the values of the `CLICOLOR_FORCE` and `NO_COLOR` environment
+/// variables.
+///
+/// Empty variables are treated as unset ones.
+#[cfg(feature = "color")]
+fn auto_colors(clicolor_force: Option<&OsStr>, no_color: Option<&OsStr>) -> bool {
+    let forced = clicolor_force.is_some_and(|value| !value.is_empty() && value != "0");
+    forced || no_color.is_none_or(OsStr::is_empty)
+}
+
+/// The SGR codes for the parts of the output.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Palette {
+    headline: Option<&'static str>,
+    header: Option<&'static str>,
+    guide: Option<&'static str>,
+}
+
+impl Palette {
+    /// Selects the palette for the given style.
+    pub(crate) fn new(style: &Style) -> Self {
+        #[cfg(feature = "color")]
+        if style.color.is_enabled() {
+            return Palette {
+                headline: Some("1;31"),
+                header: Some("2"),
+                guide: Some("36"),
+            };
+        }
+        #[cfg(not(feature = "color"))]
+        let _ = style;
+        Palette::default()
+    }
+
+    /// Paints the first displayed error.
+    pub(crate) fn headline<T>(&self, value: T) -> Painted<T> {
+        Painted {
+            code: self.headline,
+            value,
+        }
+    }
+
+    /// Paints the header.
+    pub(crate) fn header<T>(&self, value: T) -> Painted<T> {
+        Painted {
+            code: self.header,
+            value,
+        }
+    }
+
+    /// Paints the prefixes, numbers and tree guides.
+    pub(crate) fn guide<T>(&self, value: T) -> Painted<T> {
+        Painted {
+            code: self.guide,
+            value,
+        }
+    }
+}
+
+/// A value which is displayed with an optional SGR code.
+pub(crate) struct Painted<T> {
+    code: Option<&'static str>,
+    value: T,
+}
+
+impl<T> fmt::Display for Painted<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "\x1b[{}m{}\x1b[0m", code, self.value),
+            None => fmt::Display::fmt(&self.value, f),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "color"))]
+mod test {
+    use crate::{ColorChoice, ErrorChainExt as _, Style};
+    use std::ffi::OsStr;
+
+    #[test]
+    fn layouts() {
+        let error = std::io::Error::other("oops");
+
+        let style = Style::numbered().color(ColorChoice::Always);
+        let formatted = error.chain_with(style).to_string();
+        assert_eq!(formatted, "\x1b[36m0:\x1b[0m \x1b[1;31moops\x1b[0m");
+
+        let style = Style::tree().color(ColorChoice::Never);
+        let formatted = error.chain_with(style).to_string();
+        assert_eq!(formatted, "oops");
+    }
+
+    #[test]
+    fn environment() {
+        let auto = |clicolor_force: Option<&str>, no_color: Option<&str>| {
+            super::auto_colors(clicolor_force.map(OsStr::new), no_color.map(OsStr::new))
+        };
+
+        assert!(auto(None, None));
+        assert!(auto(None, Some("")));
+        assert!(!auto(None, Some("1")));
+        assert!(!auto(None, Some("0")));
+
+        assert!(auto(Some("1"), None));
+        assert!(auto(Some("0"), None));
+        assert!(auto(Some(""), None));
+
+        assert!(auto(Some("1"), Some("1")));
+        assert!(!auto(Some("0"), Some("1")));
+        assert!(!auto(Some(""), Some("1")));
+    }
+}
@@ -0,0 +1,114 @@
+//! An [`eyre`] handler which formats reports as error chains.
+//!
+//! Install the handler at the beginning of `main`, and the reports returned
+//! from it are printed out exactly as a [`DisplayErrorChain`] would print
+//! them:
+//!
+//! ```rust,no_run
+//! use eyre::WrapErr as _;
+//!
+//! fn main() -> eyre::Result<()> {
+//!     display_error_chain::eyre::install()?;
+//!
+//!     let config = std::fs::read_to_string("/definitely/missing.toml")
+//!         .wrap_err("failed to read the config")?;
+//!     println!("{config}");
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Prints out the following before exiting:
+//!
+//! ```text
+//! Error: failed to read the config
+//! Caused by:
+//!   -> No such file or directory (os error 2)
+//! ```
+
+use std::{error::Error, fmt};
+
+use ::eyre::InstallError;
+
+use crate::{DisplayErrorChain, Style};
+
+/// An [`EyreHandler`](::eyre::EyreHandler) which formats the reports using
+/// the given [`Style`].
+///
+/// The [`Debug`][fmt::Debug] representation of a report is the whole chain,
+/// and its alternate form (`{:#?}`) is the chain on a single line. The
+/// [`Display`][fmt::Display] representation is the top-level message only,
+/// and its alternate form (`{:#}`) is the chain on a single line as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Handler {
+    style: Style,
+}
+
+impl Handler {
+    /// Initializes the handler with the given style.
+    pub fn new(style: Style) -> Self {
+        Handler { style }
+    }
+
+    /// Returns the [`Style`] the reports are formatted with.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+}
+
+impl ::eyre::EyreHandler for Handler {
+    fn debug(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&DisplayErrorChain::new(error).with_style(self.style), f)
+    }
+
+    fn display(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            self.debug(error, f)
+        } else {
+            fmt::Display::fmt(error, f)
+        }
+    }
+}
+
+/// Installs the [`Handler`] with the default [`Style`] as the global
+/// [`eyre`] hook.
+///
+/// Fails if a hook has already been installed, or any report has already
+/// been created with the `auto-install` feature of [`eyre`].
+pub fn install() -> Result<(), InstallError> {
+    install_with(Style::new())
+}
+
+/// Installs the [`Handler`] with the given [`Style`] as the global
+/// [`eyre`] hook.
+///
+/// Fails if a hook has already been installed, or any report has already
+/// been created with the `auto-install` feature of [`eyre`].
+pub fn install_with(style: Style) -> Result<(), InstallError> {
+    ::eyre::set_hook(Box::new(move |_| Box::new(Handler::new(style))))
+}
+
+#[cfg(test)]
+mod test {
+    use ::eyre::WrapErr as _;
+    use std::io;
+
+    use crate::Style;
+
+    #[test]
+    fn formats_reports() {
+        super::install_with(Style::new().single_line_separator(" <- ")).unwrap();
+        assert!(super::install().is_err());
+
+        let report = Err::<(), _>(io::Error::other("oops"))
+            .wrap_err("wrapper")
+            .wrap_err("top level")
+            .unwrap_err();
+        assert_eq!(
+            format!("{:?}", report),
+            "top level\nCaused by:\n  -> wrapper\n  -> oops"
+        );
+        assert_eq!(format!("{:#?}", report), "top level <- wrapper <- oops");
+        assert_eq!(format!("{}", report), "top level");
+        assert_eq!(format!("{:#}", report), "top level <- wrapper <- oops");
+    }
+}
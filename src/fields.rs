@@ -0,0 +1,164 @@
+use alloc::format;
+use core::error::Error;
+
+use tracing::{field, Span};
+
+use crate::{frames::Frames, DisplayErrorChain};
+
+impl<E> DisplayErrorChain<E>
+where
+    E: Error,
+{
+    /// Formats the chain as a [`tracing`] field value.
+    ///
+    /// The whole chain is recorded, including the sources:
+    ///
+    /// ```rust
+    /// use display_error_chain::ErrorChainExt as _;
+    ///
+    /// # let error = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file");
+    /// tracing::error!(error = error.chain().as_value(), "failed to read the config");
+    /// ```
+    ///
+    /// It's the same as recording the chain with the `%` sigil, i.e.
+    /// `error = %error.chain()`.
+    pub fn as_value(&self) -> field::DisplayValue<&Self> {
+        field::display(self)
+    }
+
+    /// Records the chain as the value of the given field of the span.
+    ///
+    /// The field has to be declared when the span is created, since
+    /// [`tracing`] silently ignores undeclared fields:
+    ///
+    /// ```rust
+    /// use display_error_chain::ErrorChainExt as _;
+    ///
+    /// let span = tracing::info_span!("load_config", error = tracing::field::Empty);
+    /// # let error = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file");
+    /// error.chain().record(&span, "error");
+    /// ```
+    pub fn record(&self, span: &Span, field: &str) {
+        span.record(field, self.as_value());
+    }
+
+    /// Records every frame of the chain as its own field of the span, i.e.
+    /// the top-level error as `<field>.0`, its source as `<field>.1` and so
+    /// on.
+    ///
+    /// The frames are produced the same way as for formatting, so the
+    /// [maximum depth][crate::Style::max_depth] and the deduplication are
+    /// taken into account, and the omitted sources or a cycle occupy a field
+    /// of their own. Only the fields which are declared when the span is
+    /// created are recorded, and their names have to be quoted:
+    ///
+    /// ```rust
+    /// use display_error_chain::ErrorChainExt as _;
+    ///
+    /// let span = tracing::info_span!(
+    ///     "load_config",
+    ///     "error.0" = tracing::field::Empty,
+    ///     "error.1" = tracing::field::Empty,
+    ///     "error.2" = tracing::field::Empty,
+    /// );
+    /// # let error = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file");
+    /// error.chain().record_frames(&span, "error");
+    /// ```
+    pub fn record_frames(&self, span: &Span, field: &str) {
+        for (idx, frame) in Frames::new(&self.error, &self.style).enumerate() {
+            span.record(format!("{}.{}", field, idx).as_str(), field::display(frame));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fmt, io,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    use crate::{fixtures::Wrapper, ErrorChainExt as _, Style};
+
+    /// Collects the values recorded into spans.
+    #[derive(Default)]
+    struct Collector {
+        next_id: AtomicU64,
+        values: Mutex<Vec<(String, String)>>,
+    }
+
+    impl Visit for &Collector {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.values
+                .lock()
+                .unwrap()
+                .push((field.name().to_owned(), format!("{:?}", value)));
+        }
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _span: &span::Id, values: &span::Record<'_>) {
+            values.record(&mut &*self);
+        }
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, _span: &span::Id) {}
+
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    #[test]
+    fn records_fields() {
+        let collector = Arc::new(Collector::default());
+        let error = Wrapper(io::Error::other("oops"));
+        tracing::subscriber::with_default(collector.clone(), || {
+            let span = tracing::info_span!("test", error = tracing::field::Empty);
+            error.chain().record(&span, "error");
+
+            let span = tracing::info_span!(
+                "test",
+                "error.0" = tracing::field::Empty,
+                "error.1" = tracing::field::Empty,
+            );
+            error.chain().record_frames(&span, "error");
+            error
+                .chain_with(Style::new().max_depth(0))
+                .record_frames(&span, "error");
+        });
+
+        let values = collector.values.lock().unwrap();
+        let values: Vec<_> = values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("error", "wrapper\nCaused by:\n  -> oops"),
+                ("error.0", "wrapper"),
+                ("error.1", "oops"),
+                ("error.0", "wrapper"),
+                ("error.1", "... 1 more cause omitted"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Errors shared by the tests of several modules.
+
+use std::{error::Error, fmt, io};
+
+/// An error which is caused by an [`io::Error`].
+#[derive(Debug)]
+pub(crate) struct Wrapper(pub(crate) io::Error);
+
+impl fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "wrapper")
+    }
+}
+
+impl Error for Wrapper {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
@@ -0,0 +1,178 @@
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::{self, Vec},
+};
+use core::{error::Error, fmt, mem};
+
+use crate::{Chain, Style};
+
+/// A single entry of a formatted error chain.
+#[derive(Clone, Copy)]
+pub(crate) enum Frame<'a> {
+    /// An error from the chain.
+    Error(&'a dyn Error),
+    /// The given number of errors is not displayed since the chain is too
+    /// deep.
+    Omitted(usize),
+    /// The next source is an error which has already been visited, i.e. the
+    /// chain would never end.
+    Cycle,
+}
+
+impl fmt::Display for Frame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Frame::Error(error) => fmt::Display::fmt(error, f),
+            Frame::Omitted(1) => write!(f, "... 1 more cause omitted"),
+            Frame::Omitted(count) => write!(f, "... {} more causes omitted", count),
+            Frame::Cycle => write!(f, "<cycle detected>"),
+        }
+    }
+}
+
+/// Walks an error and its sources, producing the frames to be formatted.
+///
+/// The top-level error is always produced as the first frame.
+#[derive(Clone)]
+pub(crate) struct Frames<'a> {
+    chain: Chain<'a>,
+    /// Whether the [cycle][Frame::Cycle] marker is yet to be produced.
+    cycle: bool,
+    /// The number of errors produced so far.
+    depth: usize,
+    max_depth: Option<usize>,
+    /// The message of the previously walked error, if deduplication is
+    /// enabled.
+    #[cfg(feature = "alloc")]
+    parent_message: Option<String>,
+    #[cfg(feature = "alloc")]
+    deduplicate: bool,
+}
+
+impl<'a> Frames<'a> {
+    /// Starts walking the chain from the given error.
+    pub(crate) fn new(error: &'a dyn Error, style: &Style) -> Self {
+        let chain = Chain::new(error);
+        Frames {
+            cycle: chain.is_cyclic(),
+            chain,
+            depth: 0,
+            max_depth: style.max_depth,
+            #[cfg(feature = "alloc")]
+            parent_message: None,
+            #[cfg(feature = "alloc")]
+            deduplicate: style.deduplicate,
+        }
+    }
+
+    /// Produces the same frames in the reverse order.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn reversed(self) -> Reversed<'a> {
+        Reversed {
+            frames: self.collect::<Vec<_>>().into_iter(),
+        }
+    }
+
+    /// Produces the same frames in the reverse order.
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn reversed(self) -> Reversed<'a> {
+        Reversed {
+            remaining: self.clone().count(),
+            frames: self,
+        }
+    }
+
+    /// Skips the rest of the chain, and returns the number of the skipped
+    /// errors which would have been displayed otherwise, i.e. the redundant
+    /// ones are not counted.
+    fn omit_rest(&mut self) -> usize {
+        let chain = mem::replace(&mut self.chain, Chain::empty());
+        #[cfg(feature = "alloc")]
+        if self.deduplicate {
+            return chain.filter(|&error| !self.is_redundant(error)).count();
+        }
+        chain.count()
+    }
+
+    /// Checks whether the error's message is already included into the
+    /// message of its parent, and remembers the message for the next error.
+    #[cfg(feature = "alloc")]
+    fn is_redundant(&mut self, error: &dyn Error) -> bool {
+        if !self.deduplicate {
+            return false;
+        }
+        let message = error.to_string();
+        let redundant = self
+            .parent_message
+            .as_deref()
+            .is_some_and(|parent_message| {
+                !message.is_empty() && parent_message.contains(message.as_str())
+            });
+        self.parent_message = Some(message);
+        redundant
+    }
+
+    /// Deduplication requires allocations, so nothing is redundant without
+    /// the `alloc` feature.
+    #[cfg(not(feature = "alloc"))]
+    fn is_redundant(&mut self, _error: &dyn Error) -> bool {
+        false
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Frame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.chain.len() != 0
+            && self
+                .max_depth
+                .is_some_and(|max_depth| self.depth > max_depth)
+        {
+            let omitted = self.omit_rest();
+            if omitted != 0 {
+                self.cycle = false;
+                return Some(Frame::Omitted(omitted));
+            }
+        }
+        while let Some(error) = self.chain.next() {
+            if !self.is_redundant(error) {
+                self.depth += 1;
+                return Some(Frame::Error(error));
+            }
+        }
+        mem::take(&mut self.cycle).then_some(Frame::Cycle)
+    }
+}
+
+/// Produces the frames of a walk in the reverse order, i.e. starting from the
+/// root cause.
+///
+/// The frames are collected upfront with the `alloc` feature. Otherwise, the
+/// walk is restarted for every frame, which takes quadratic time in the depth
+/// of the chain.
+#[derive(Clone)]
+pub(crate) struct Reversed<'a> {
+    #[cfg(feature = "alloc")]
+    frames: vec::IntoIter<Frame<'a>>,
+    #[cfg(not(feature = "alloc"))]
+    frames: Frames<'a>,
+    #[cfg(not(feature = "alloc"))]
+    remaining: usize,
+}
+
+impl<'a> Iterator for Reversed<'a> {
+    type Item = Frame<'a>;
+
+    #[cfg(feature = "alloc")]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.frames.next_back()
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        self.frames.clone().nth(self.remaining)
+    }
+}
@@ -0,0 +1,180 @@
+use core::fmt::{self, Write};
+
+/// A [`fmt::Write`] adapter that indents every continuation line of the text
+/// written through it.
+///
+/// The first line is written as is, since it is expected to be already
+/// positioned by the caller. Line breaks are replaced with the separator, and
+/// the indentation is only inserted before non-empty lines, so no trailing
+/// whitespace is produced.
+pub(crate) struct Indented<'a, W: ?Sized> {
+    inner: &'a mut W,
+    separator: &'a str,
+    guide: &'a str,
+    width: usize,
+    pending: bool,
+}
+
+impl<'a, W> Indented<'a, W>
+where
+    W: Write + ?Sized,
+{
+    /// Wraps the writer, separating the lines with the `separator` and
+    /// indenting the continuation lines by the `guide` followed by `width`
+    /// spaces.
+    pub(crate) fn new(inner: &'a mut W, separator: &'a str, guide: &'a str, width: usize) -> Self {
+        Indented {
+            inner,
+            separator,
+            guide,
+            width,
+            pending: false,
+        }
+    }
+}
+
+impl<W> Write for Indented<'_, W>
+where
+    W: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (idx, line) in s.split('\n').enumerate() {
+            if idx != 0 {
+                self.inner.write_str(self.separator)?;
+                self.pending = true;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if self.pending {
+                self.pending = false;
+                write!(
+                    self.inner,
+                    "{}{:width$}",
+                    self.guide,
+                    "",
+                    width = self.width
+                )?;
+            }
+            self.inner.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+/// A [`fmt::Write`] adapter that joins all the lines of the text written
+/// through it into a single one.
+///
+/// Every run of line breaks is replaced with a single space, and the line
+/// breaks at the end of the text are dropped.
+pub(crate) struct Joined<'a, W: ?Sized> {
+    inner: &'a mut W,
+    pending: bool,
+}
+
+impl<'a, W> Joined<'a, W>
+where
+    W: Write + ?Sized,
+{
+    /// Wraps the writer.
+    pub(crate) fn new(inner: &'a mut W) -> Self {
+        Joined {
+            inner,
+            pending: false,
+        }
+    }
+}
+
+impl<W> Write for Joined<'_, W>
+where
+    W: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (idx, line) in s.split(['\r', '\n']).enumerate() {
+            if idx != 0 {
+                self.pending = true;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if self.pending {
+                self.pending = false;
+                self.inner.write_char(' ')?;
+            }
+            self.inner.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Calculates the width of the text when it's displayed.
+///
+/// Without the `unicode-width` feature every character is considered to
+/// occupy a single column.
+pub(crate) fn display_width(s: &str) -> usize {
+    #[cfg(feature = "unicode-width")]
+    {
+        unicode_width::UnicodeWidthStr::width(s)
+    }
+    #[cfg(not(feature = "unicode-width"))]
+    {
+        s.chars().count()
+    }
+}
+
+/// Calculates the width of the character when it's displayed.
+///
+/// Without the `unicode-width` feature every character is considered to
+/// occupy a single column.
+#[cfg(feature = "alloc")]
+pub(crate) fn char_width(c: char) -> usize {
+    #[cfg(feature = "unicode-width")]
+    {
+        unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
+    }
+    #[cfg(not(feature = "unicode-width"))]
+    {
+        let _ = c;
+        1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Indented, Joined};
+    use std::fmt::Write as _;
+
+    #[test]
+    fn indents_continuation_lines() {
+        let mut output = String::new();
+        let mut indented = Indented::new(&mut output, "\n", "", 4);
+        write!(indented, "first\nsecond\n\nthi").unwrap();
+        indented.write_str("rd\n").unwrap();
+        write!(indented, "fourth").unwrap();
+        assert_eq!(output, "first\n    second\n\n    third\n    fourth");
+
+        let mut output = String::new();
+        let mut indented = Indented::new(&mut output, "\r\n", "|", 2);
+        write!(indented, "first\nsecond").unwrap();
+        assert_eq!(output, "first\r\n|  second");
+    }
+
+    #[test]
+    fn joins_lines() {
+        let mut output = String::new();
+        let mut joined = Joined::new(&mut output);
+        write!(joined, "first\nsecond\r\n\nthi").unwrap();
+        joined.write_str("rd\n").unwrap();
+        writeln!(joined, "fourth").unwrap();
+        assert_eq!(output, "first second third fourth");
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn wide_characters() {
+        assert_eq!(super::display_width("エラー"), 6);
+        assert_eq!(super::display_width("├─ "), 3);
+        #[cfg(feature = "alloc")]
+        assert_eq!(super::char_width('😱'), 2);
+    }
+}
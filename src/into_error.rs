@@ -0,0 +1,139 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::error::Error;
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+/// A conversion into an [`Error`] which can be formatted as a chain.
+///
+/// It's implemented for every [`Error`], and for the types which only act as
+/// errors without implementing the trait themselves, like the boxed error
+/// trait objects (`Box<dyn Error + Send + Sync>` and the like) or
+/// `anyhow::Error`. The `M` parameter only tells the implementations apart,
+/// and is always inferred.
+pub trait IntoError<M: ?Sized> {
+    /// The error the value is converted into.
+    type Error: Error;
+
+    /// Converts the value into an error.
+    fn into_error(self) -> Self::Error;
+
+    /// Borrows the value as an error.
+    fn as_error(&self) -> &(dyn Error + '_);
+}
+
+impl<E> IntoError<E> for E
+where
+    E: Error,
+{
+    type Error = E;
+
+    fn into_error(self) -> Self::Error {
+        self
+    }
+
+    fn as_error(&self) -> &(dyn Error + '_) {
+        self
+    }
+}
+
+/// A boxed error trait object, which implements [`Error`] by delegating to
+/// the boxed error.
+///
+/// The [`Display`][fmt::Display], [`Debug`][fmt::Debug] and
+/// [`Error::source`] implementations are transparent, so the chain is exactly
+/// the same as the one of the boxed error.
+///
+/// That's what a boxed error is converted into by [`IntoError`], so it can be
+/// wrapped into a [`DisplayErrorChain`][crate::DisplayErrorChain]:
+///
+/// ```rust
+/// use display_error_chain::DisplayErrorChain;
+///
+/// let error: Box<dyn std::error::Error + Send + Sync> = "out of coffee".into();
+/// let chain = DisplayErrorChain::from_into_error(error);
+/// assert_eq!("out of coffee", chain.to_string());
+/// ```
+#[cfg(feature = "alloc")]
+pub struct BoxedError<T: ?Sized>(Box<T>);
+
+#[cfg(feature = "alloc")]
+impl<T> BoxedError<T>
+where
+    T: Error + ?Sized,
+{
+    /// Deconstructs the [`BoxedError`] and returns the boxed error.
+    pub fn into_inner(self) -> Box<T> {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> fmt::Debug for BoxedError<T>
+where
+    T: Error + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> fmt::Display for BoxedError<T>
+where
+    T: Error + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Error for BoxedError<T>
+where
+    T: Error + ?Sized,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Implements [`IntoError`] for the boxed error trait objects.
+#[cfg(feature = "alloc")]
+macro_rules! impl_boxed {
+    ($($object:ty),* $(,)?) => {
+        $(
+            impl<'a> IntoError<$object> for Box<$object> {
+                type Error = BoxedError<$object>;
+
+                fn into_error(self) -> Self::Error {
+                    BoxedError(self)
+                }
+
+                fn as_error(&self) -> &(dyn Error + '_) {
+                    &**self
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl_boxed!(
+    dyn Error + 'a,
+    dyn Error + Send + 'a,
+    dyn Error + Send + Sync + 'a,
+);
+
+#[cfg(feature = "anyhow")]
+impl IntoError<dyn Error + Send + Sync> for anyhow::Error {
+    type Error = BoxedError<dyn Error + Send + Sync>;
+
+    fn into_error(self) -> Self::Error {
+        BoxedError(self.into())
+    }
+
+    fn as_error(&self) -> &(dyn Error + '_) {
+        &**self
+    }
+}
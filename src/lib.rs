@@ -69,13 +69,114 @@
 //!   -> mid level
 //!   -> low level"
 //! );
+//!
+//! // The whole chain can also be squeezed into a single line:
+//! let formatted = TopLevel.chain().single_line().to_string();
+//! assert_eq!(formatted, "top level: mid level: low level");
+//!
+//! // ... or simply with the alternate flag:
+//! let formatted = format!("{:#}", TopLevel.chain());
+//! assert_eq!(formatted, "top level: mid level: low level");
 //! ```
+//!
+//! ## Cargo features
+//!
+//! The crate supports `#![no_std]` environments: the formatting only relies on
+//! `core::error::Error`, so just disable the default features.
+//!
+//! * `std` (enabled by default): enables `ResultExt`, `ChainReport` and `Report`.
+//!   Implies `alloc`.
+//! * `alloc`: enables `OwnedErrorChain`, and the deduplication and wrapping of
+//!   the messages, see `Style::deduplicate` and `Style::wrap`.
+//! * `color`: enables colored output, see `Style::color`. Implies `std`.
+//! * `unicode-width`: takes the display width of Unicode characters into account
+//!   when aligning and wrapping the messages.
+//! * `serde`: serializes `DisplayErrorChain` and `OwnedErrorChain` as a sequence
+//!   of frames, i.e. `[{"message": "..."}, ...]`, and deserializes
+//!   `OwnedErrorChain` back. Implies `alloc`.
+//! * `log`: enables logging of error chains with the `log_chain!` family of
+//!   macros, and, together with `std`, with `ResultExt::log_err_chain` and
+//!   `ResultExt::ok_or_log_chain`.
+//! * `tracing`: enables recording of error chains as `tracing` fields, see
+//!   `DisplayErrorChain::record` and `DisplayErrorChain::record_frames`. Implies
+//!   `alloc`.
+//! * `eyre`: provides an `eyre` handler which formats the reports as error
+//!   chains, see the `eyre` module. Implies `std`.
+//! * `anyhow`: enables formatting of `anyhow::Error` as an error chain with
+//!   `DisplayErrorChain::from_into_error` and `ResultExt`, see `IntoError`.
+//!   Implies `std`.
+
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::{error::Error, fmt};
+
+mod as_dyn_error;
+use as_dyn_error::AsDynError;
+
+mod chain;
+use chain::static_sources;
+pub use chain::Chain;
+
+#[cfg(feature = "eyre")]
+pub mod eyre;
+
+#[cfg(feature = "tracing")]
+mod fields;
+
+#[cfg(all(test, any(feature = "std", feature = "serde", feature = "tracing")))]
+mod fixtures;
 
-use std::{error::Error, fmt};
+mod color;
+#[cfg(feature = "color")]
+pub use color::ColorChoice;
 
+mod frames;
+mod indent;
+
+mod into_error;
+#[cfg(feature = "alloc")]
+pub use into_error::BoxedError;
+pub use into_error::IntoError;
+
+#[cfg(feature = "log")]
+mod logging;
+
+/// Not a public API, used by the macros.
+#[cfg(feature = "log")]
+#[doc(hidden)]
+pub mod __private {
+    pub use log;
+}
+
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "alloc")]
+pub use owned::OwnedErrorChain;
+
+mod render;
+
+#[cfg(feature = "serde")]
+mod serialization;
+
+#[cfg(feature = "std")]
+mod report;
+#[cfg(feature = "std")]
+pub use report::{ChainReport, Report};
+
+#[cfg(feature = "alloc")]
+mod wrap;
+
+#[cfg(feature = "std")]
 mod result_ext;
+#[cfg(feature = "std")]
 pub use result_ext::ResultExt;
 
+mod style;
+pub use style::{DebugOutput, Layout, Style};
+
 /// Provides an [fmt::Display] implementation for an error as a chain.
 ///
 /// ```rust
@@ -137,13 +238,44 @@ pub use result_ext::ResultExt;
 /// assert_eq!("No cause", chain.to_string());
 /// ```
 ///
-/// Other standard traits (like [`Debug`][std::fmt::Debug], [`Clone`] and some
+/// The layout of the output is controlled by a [`Style`], which can be set with
+/// the [`DisplayErrorChain::with_style`] method. The alternate flag (`{:#}`)
+/// overrides the configured [`Layout`] with the compact
+/// [single line][Layout::SingleLine] one:
+///
+/// ```rust
+/// # use display_error_chain::ErrorChainExt as _;
+/// # #[derive(Debug)]
+/// # struct Wrapper(std::io::Error);
+/// # impl std::fmt::Display for Wrapper {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         write!(f, "Some I/O")
+/// #     }
+/// # }
+/// # impl std::error::Error for Wrapper {
+/// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+/// #         Some(&self.0)
+/// #     }
+/// # }
+/// let error = Wrapper(std::io::Error::new(std::io::ErrorKind::Other, "wow"));
+/// assert_eq!("Some I/O\nCaused by:\n  -> wow", format!("{}", error.chain()));
+/// assert_eq!("Some I/O: wow", format!("{:#}", error.chain()));
+/// ```
+///
+/// A faulty [`Error::source`] implementation might produce a cycle of errors.
+/// The chain is cut at the first error that has already been displayed, and a
+/// `<cycle detected>` marker is printed in its place.
+///
+/// Other standard traits (like [`Debug`][core::fmt::Debug], [`Clone`] and some
 /// others) are automatically derived for the convenience using the standard
 /// derive macros. If you need another trait, feel free to submit a PR and/or
 /// use the [`DisplayErrorChain::into_inner`] method to access the wrapped
 /// error.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DisplayErrorChain<E>(E);
+pub struct DisplayErrorChain<E> {
+    error: E,
+    style: Style,
+}
 
 impl<E: Error> From<E> for DisplayErrorChain<E> {
     fn from(value: E) -> Self {
@@ -157,12 +289,56 @@ where
 {
     /// Initializes the formatter with the error provided.
     pub fn new(error: E) -> Self {
-        DisplayErrorChain(error)
+        DisplayErrorChain {
+            error,
+            style: Style::new(),
+        }
+    }
+
+    /// Initializes the formatter with anything which can be converted into an
+    /// error, e.g. a `Box<dyn Error + Send + Sync>`, see [`IntoError`].
+    pub fn from_into_error<T, M>(error: T) -> Self
+    where
+        T: IntoError<M, Error = E>,
+        M: ?Sized,
+    {
+        DisplayErrorChain::new(error.into_error())
+    }
+
+    /// Replaces the [`Style`] the error chain is formatted with.
+    pub fn with_style(self, style: Style) -> Self {
+        DisplayErrorChain { style, ..self }
+    }
+
+    /// Switches to the [single line][Layout::SingleLine] layout, keeping the
+    /// rest of the [`Style`] intact.
+    ///
+    /// ```rust
+    /// # use std::io;
+    /// use display_error_chain::DisplayErrorChain;
+    ///
+    /// let error = io::Error::new(io::ErrorKind::Other, "out of coffee");
+    /// let chain = DisplayErrorChain::new(&error).single_line();
+    /// assert_eq!("out of coffee", chain.to_string());
+    /// ```
+    pub fn single_line(self) -> Self {
+        let style = self.style.layout(Layout::SingleLine);
+        self.with_style(style)
+    }
+
+    /// Returns the [`Style`] the error chain is formatted with.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Returns an iterator over the wrapped error and its sources.
+    pub fn iter(&self) -> Chain<'_> {
+        Chain::new(&self.error)
     }
 
     /// Deconstructs the [`DisplayErrorChain`] and returns the wrapped error.
     pub fn into_inner(self) -> E {
-        self.0
+        self.error
     }
 }
 
@@ -171,25 +347,20 @@ where
     E: Error,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)?;
-
-        let mut cause_printed = false;
-        let mut source = self.0.source();
-        while let Some(cause) = source {
-            if !cause_printed {
-                cause_printed = true;
-                writeln!(f, "\nCaused by:")?;
-            } else {
-                writeln!(f)?
-            }
-            write!(f, "  -> {}", cause)?;
-            source = cause.source();
-        }
-        Ok(())
+        let layout = if f.alternate() {
+            Layout::SingleLine
+        } else {
+            self.style.layout
+        };
+        render::render(&self.error, &self.style, layout, f)
     }
 }
 
 /// An extension trait for [`Error`] types to display their sources in a chain.
+///
+/// Besides the errors themselves, it is implemented for the `dyn Error`
+/// trait objects, so it can be used on a `&dyn Error` or on a
+/// `Box<dyn Error + Send + Sync>` as well.
 pub trait ErrorChainExt {
     /// Provides an [fmt::Display] implementation for an error as a chain.
     fn chain(&self) -> DisplayErrorChain<&Self>;
@@ -198,20 +369,691 @@ pub trait ErrorChainExt {
     fn into_chain(self) -> DisplayErrorChain<Self>
     where
         Self: Sized;
+
+    /// Same as [`chain`][ErrorChainExt::chain], but formats the chain with
+    /// the given [`Style`].
+    fn chain_with(&self, style: Style) -> DisplayErrorChain<&Self>;
+
+    /// Same as [`chain_with`][ErrorChainExt::chain_with], but consumes
+    /// `self`.
+    fn into_chain_with(self, style: Style) -> DisplayErrorChain<Self>
+    where
+        Self: Sized;
+
+    /// Returns an iterator over the error and its sources.
+    fn sources(&self) -> Chain<'_>;
+
+    /// Returns the deepest source of the error, or the error itself if it
+    /// has no source.
+    ///
+    /// ```rust
+    /// use display_error_chain::ErrorChainExt as _;
+    ///
+    /// # #[derive(Debug)]
+    /// # struct ConfigError(std::io::Error);
+    /// # impl std::fmt::Display for ConfigError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "failed to read the config")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for ConfigError {
+    /// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// #         Some(&self.0)
+    /// #     }
+    /// # }
+    /// let error = ConfigError(std::io::Error::new(
+    ///     std::io::ErrorKind::NotFound,
+    ///     "no such file",
+    /// ));
+    /// assert_eq!(error.root_cause().to_string(), "no such file");
+    ///
+    /// let io = error.find_source::<std::io::Error>().unwrap();
+    /// assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+    ///
+    /// assert!(error.any_source(|error| error.to_string().contains("config")));
+    /// ```
+    fn root_cause(&self) -> &dyn Error;
+
+    /// Returns the first error of the chain (starting from the error itself)
+    /// which is of type `T`.
+    fn find_source<T>(&self) -> Option<&T>
+    where
+        T: Error + 'static,
+        Self: 'static;
+
+    /// Checks whether any error of the chain (starting from the error itself)
+    /// satisfies the predicate.
+    fn any_source<F>(&self, predicate: F) -> bool
+    where
+        F: FnMut(&dyn Error) -> bool;
 }
 
 impl<E> ErrorChainExt for E
 where
-    E: Error,
+    E: AsDynError + ?Sized,
 {
     fn chain(&self) -> DisplayErrorChain<&Self> {
-        DisplayErrorChain::new(self)
+        DisplayErrorChain {
+            error: self,
+            style: Style::new(),
+        }
     }
 
     fn into_chain(self) -> DisplayErrorChain<Self>
     where
         Self: Sized,
     {
-        DisplayErrorChain::new(self)
+        DisplayErrorChain {
+            error: self,
+            style: Style::new(),
+        }
+    }
+
+    fn chain_with(&self, style: Style) -> DisplayErrorChain<&Self> {
+        DisplayErrorChain {
+            style,
+            ..self.chain()
+        }
+    }
+
+    fn into_chain_with(self, style: Style) -> DisplayErrorChain<Self>
+    where
+        Self: Sized,
+    {
+        DisplayErrorChain {
+            style,
+            ..self.into_chain()
+        }
+    }
+
+    fn sources(&self) -> Chain<'_> {
+        Chain::new(self.as_dyn_error())
+    }
+
+    fn root_cause(&self) -> &dyn Error {
+        self.sources().last().unwrap_or(self.as_dyn_error())
+    }
+
+    fn find_source<T>(&self) -> Option<&T>
+    where
+        T: Error + 'static,
+        Self: 'static,
+    {
+        static_sources(self.as_static_error()).find_map(|error| error.downcast_ref())
+    }
+
+    fn any_source<F>(&self, predicate: F) -> bool
+    where
+        F: FnMut(&dyn Error) -> bool,
+    {
+        self.sources().any(predicate)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    use super::ChainReport;
+    #[cfg(feature = "alloc")]
+    use super::OwnedErrorChain;
+    use super::{DebugOutput, DisplayErrorChain, ErrorChainExt as _, Style};
+    use std::{error::Error, fmt};
+
+    /// An error with the given number of nested sources.
+    #[derive(Debug, Clone, Copy)]
+    struct Nested(usize);
+
+    /// The number of the [`Nested`] errors which can be used as sources.
+    const DEPTH: usize = 20_000;
+
+    impl fmt::Display for Nested {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "level {}", self.0)
+        }
+    }
+
+    impl Error for Nested {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            static LEVELS: [Nested; DEPTH] = {
+                let mut levels = [Nested(0); DEPTH];
+                let mut level = 0;
+                while level != DEPTH {
+                    levels[level] = Nested(level);
+                    level += 1;
+                }
+                levels
+            };
+            self.0.checked_sub(1).map(|level| &LEVELS[level] as _)
+        }
+    }
+
+    #[test]
+    fn numbered_alignment() {
+        let formatted = Nested(10).chain_with(Style::numbered()).to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                " 0: level 10\n",
+                " 1: level 9\n",
+                " 2: level 8\n",
+                " 3: level 7\n",
+                " 4: level 6\n",
+                " 5: level 5\n",
+                " 6: level 4\n",
+                " 7: level 3\n",
+                " 8: level 2\n",
+                " 9: level 1\n",
+                "10: level 0",
+            )
+        );
+    }
+
+    /// An error with a multi-line message caused by another such error.
+    #[derive(Debug)]
+    struct MultiLine(Option<&'static MultiLine>);
+
+    impl fmt::Display for MultiLine {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "first line\nsecond line")
+        }
+    }
+
+    impl Error for MultiLine {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.0.map(|source| source as _)
+        }
+    }
+
+    static MULTI_LINE: MultiLine = MultiLine(Some(&MultiLine(None)));
+    static MULTI_LINE_DEEP: MultiLine = MultiLine(Some(&MULTI_LINE));
+
+    #[test]
+    fn multiline_messages() {
+        let formatted = MULTI_LINE.chain().to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                "first line\n",
+                "second line\n",
+                "Caused by:\n",
+                "  -> first line\n",
+                "     second line",
+            )
+        );
+
+        let formatted = MULTI_LINE.chain_with(Style::numbered()).to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                "0: first line\n",
+                "   second line\n",
+                "1: first line\n",
+                "   second line",
+            )
+        );
+
+        let formatted = format!("{:#}", MULTI_LINE.chain());
+        assert_eq!(formatted, "first line second line: first line second line");
+
+        let style = Style::new().separator("\r\n");
+        let formatted = MULTI_LINE.chain_with(style).to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                "first line\r\n",
+                "second line\r\n",
+                "Caused by:\r\n",
+                "  -> first line\r\n",
+                "     second line",
+            )
+        );
+    }
+
+    #[test]
+    fn max_depth() {
+        let style = Style::numbered().max_depth(9);
+        let formatted = Nested(11).chain_with(style).to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                "0: level 11\n",
+                "1: level 10\n",
+                "2: level 9\n",
+                "3: level 8\n",
+                "4: level 7\n",
+                "5: level 6\n",
+                "6: level 5\n",
+                "7: level 4\n",
+                "8: level 3\n",
+                "9: level 2\n",
+                "   ... 2 more causes omitted",
+            )
+        );
+
+        let style = Style::single_line().max_depth(0);
+        let formatted = Nested(1).chain_with(style).to_string();
+        assert_eq!(formatted, "level 1: ... 1 more cause omitted");
+
+        let style = Style::new().max_depth(2);
+        let formatted = Nested(2).chain_with(style).to_string();
+        assert_eq!(formatted, "level 2\nCaused by:\n  -> level 1\n  -> level 0");
+    }
+
+    #[test]
+    fn deep_chain() {
+        let error = Nested(DEPTH);
+        assert_eq!(error.sources().len(), DEPTH + 1);
+
+        let style = Style::new().max_depth(3);
+        let formatted = error.chain_with(style).to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                "level 20000\n",
+                "Caused by:\n",
+                "  -> level 19999\n",
+                "  -> level 19998\n",
+                "  -> level 19997\n",
+                "     ... 19997 more causes omitted",
+            )
+        );
+
+        let style = Style::single_line().max_depth(1).reverse(true);
+        let formatted = error.chain_with(style).to_string();
+        assert_eq!(
+            formatted,
+            "... 19999 more causes omitted: level 19999: level 20000"
+        );
+    }
+
+    /// An error which is its own source.
+    #[derive(Debug)]
+    struct Cyclic;
+
+    impl fmt::Display for Cyclic {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "cyclic")
+        }
+    }
+
+    impl Error for Cyclic {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(self)
+        }
+    }
+
+    /// An error which has a cyclic source.
+    #[derive(Debug)]
+    struct CyclicSource;
+
+    impl fmt::Display for CyclicSource {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "cyclic source")
+        }
+    }
+
+    impl Error for CyclicSource {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&Cyclic)
+        }
+    }
+
+    /// An error which wraps another one, sharing the address and the
+    /// [`Debug`][fmt::Debug] representation with it.
+    struct Outer(Inner);
+
+    #[derive(Debug)]
+    struct Inner;
+
+    impl fmt::Debug for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&self.0, f)
+        }
+    }
+
+    impl fmt::Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "outer")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    impl fmt::Display for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "inner")
+        }
+    }
+
+    impl Error for Inner {}
+
+    #[test]
+    fn into_error() {
+        // The error type is inferred from the annotation, as `new` only
+        // accepts errors.
+        let chain: DisplayErrorChain<std::io::Error> =
+            DisplayErrorChain::new(std::io::ErrorKind::NotFound.into());
+        assert_eq!(chain.to_string(), "entity not found");
+
+        #[cfg(feature = "alloc")]
+        {
+            let error: Box<dyn Error + Send + Sync> = Box::new(Nested(1));
+            let chain = DisplayErrorChain::from_into_error(error);
+            assert_eq!(chain.to_string(), "level 1\nCaused by:\n  -> level 0");
+        }
+
+        #[cfg(feature = "anyhow")]
+        {
+            let error = anyhow::Error::new(Nested(1)).context("context");
+            let chain = DisplayErrorChain::from_into_error(error).single_line();
+            assert_eq!(chain.to_string(), "context: level 1: level 0");
+        }
+    }
+
+    #[test]
+    fn shared_addresses() {
+        let formatted = Outer(Inner).chain().to_string();
+        assert_eq!(formatted, "outer\nCaused by:\n  -> inner");
+        assert_eq!(Outer(Inner).sources().count(), 2);
+    }
+
+    #[test]
+    fn cycles() {
+        // The top-level error is a reference, i.e. a distinct error object,
+        // so the cycle is only detected once `source()` returns the error
+        // again.
+        let formatted = Cyclic.chain().to_string();
+        assert_eq!(
+            formatted,
+            "cyclic\nCaused by:\n  -> cyclic\n  -> <cycle detected>"
+        );
+        assert_eq!(Cyclic.chain().iter().count(), 2);
+
+        // Every constructor produces the same chain.
+        assert_eq!(DisplayErrorChain::new(&Cyclic).to_string(), formatted);
+        assert_eq!(DisplayErrorChain::from(&Cyclic).to_string(), formatted);
+        assert_eq!(DisplayErrorChain::new(&Cyclic).iter().count(), 2);
+        let error: &dyn Error = &Cyclic;
+        assert_eq!(error.chain().to_string(), formatted);
+
+        let formatted = CyclicSource.chain().to_string();
+        assert_eq!(
+            formatted,
+            "cyclic source\nCaused by:\n  -> cyclic\n  -> <cycle detected>"
+        );
+
+        let formatted = CyclicSource.chain_with(Style::numbered()).to_string();
+        assert_eq!(
+            formatted,
+            "0: cyclic source\n1: cyclic\n   <cycle detected>"
+        );
+
+        let formatted = format!("{:#}", CyclicSource.chain());
+        assert_eq!(formatted, "cyclic source: cyclic: <cycle detected>");
+
+        let style = Style::new().max_depth(0);
+        let formatted = CyclicSource.chain_with(style).to_string();
+        assert_eq!(
+            formatted,
+            "cyclic source\nCaused by:\n     ... 1 more cause omitted"
+        );
+    }
+
+    /// An error with a message which might include the source's message.
+    #[cfg(feature = "alloc")]
+    #[derive(Debug)]
+    struct Message {
+        message: &'static str,
+        include_source: bool,
+        source: Option<&'static Message>,
+    }
+
+    #[cfg(feature = "alloc")]
+    impl fmt::Display for Message {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.message)?;
+            match self.source {
+                Some(source) if self.include_source => write!(f, ": {}", source),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl Error for Message {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.source.map(|source| source as _)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn deduplicate() {
+        static LOW: Message = Message {
+            message: "low",
+            include_source: false,
+            source: None,
+        };
+        static SAME: Message = Message {
+            message: "low",
+            include_source: false,
+            source: Some(&LOW),
+        };
+        static MID: Message = Message {
+            message: "mid",
+            include_source: true,
+            source: Some(&SAME),
+        };
+        let top = Message {
+            message: "top",
+            include_source: false,
+            source: Some(&MID),
+        };
+
+        let formatted = top.chain().to_string();
+        assert_eq!(
+            formatted,
+            "top\nCaused by:\n  -> mid: low\n  -> low\n  -> low"
+        );
+
+        let style = Style::new().deduplicate(true);
+        let formatted = top.chain_with(style).to_string();
+        assert_eq!(formatted, "top\nCaused by:\n  -> mid: low");
+
+        let style = Style::numbered().deduplicate(true);
+        let formatted = top.chain_with(style).to_string();
+        assert_eq!(formatted, "0: top\n1: mid: low");
+
+        let style = Style::numbered().deduplicate(true).reverse(true);
+        let formatted = top.chain_with(style).to_string();
+        assert_eq!(formatted, "1: mid: low\n0: top");
+
+        // Only the errors which would have been displayed are omitted.
+        let style = Style::new().deduplicate(true).max_depth(0);
+        let formatted = top.chain_with(style).to_string();
+        assert_eq!(formatted, "top\nCaused by:\n     ... 1 more cause omitted");
+
+        let style = Style::new().deduplicate(true).max_depth(1);
+        let formatted = top.chain_with(style).to_string();
+        assert_eq!(formatted, "top\nCaused by:\n  -> mid: low");
+
+        let style = Style::single_line().deduplicate(true);
+        let formatted = CyclicSource.chain_with(style).to_string();
+        assert_eq!(formatted, "cyclic source: <cycle detected>");
+    }
+
+    #[test]
+    fn tree() {
+        let formatted = Nested(2).chain_with(Style::tree()).to_string();
+        assert_eq!(formatted, "level 2\n├─ level 1\n└─ level 0");
+
+        let formatted = MULTI_LINE.chain_with(Style::tree()).to_string();
+        assert_eq!(
+            formatted,
+            "first line\nsecond line\n└─ first line\n   second line"
+        );
+
+        let style = Style::tree().ascii(true).max_depth(1);
+        let formatted = MULTI_LINE_DEEP.chain_with(style).to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                "first line\n",
+                "second line\n",
+                "|- first line\n",
+                "|  second line\n",
+                "`- ... 1 more cause omitted",
+            )
+        );
+    }
+
+    #[test]
+    fn reverse() {
+        let formatted = Nested(2).chain_with(Style::new().reverse(true)).to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                "level 0\n",
+                "The above error caused:\n",
+                "  -> level 1\n",
+                "  -> level 2",
+            )
+        );
+
+        let style = Style::new().header("Leads to:").reverse(true);
+        let formatted = Nested(1).chain_with(style).to_string();
+        assert_eq!(formatted, "level 0\nLeads to:\n  -> level 1");
+
+        let style = Style::numbered().reverse(true);
+        let formatted = Nested(2).chain_with(style).to_string();
+        assert_eq!(formatted, "2: level 0\n1: level 1\n0: level 2");
+
+        let style = Style::single_line().reverse(true).max_depth(1);
+        let formatted = Nested(3).chain_with(style).to_string();
+        assert_eq!(formatted, "... 2 more causes omitted: level 2: level 3");
+
+        let style = Style::tree().reverse(true);
+        let formatted = CyclicSource.chain_with(style).to_string();
+        assert_eq!(formatted, "<cycle detected>\n├─ cyclic\n└─ cyclic source");
+
+        // The frames are collected once instead of walking the chain anew for
+        // every frame.
+        #[cfg(feature = "alloc")]
+        {
+            let style = Style::single_line().reverse(true);
+            let formatted = Nested(DEPTH).chain_with(style).to_string();
+            assert!(formatted.starts_with("level 0: level 1: "));
+            assert!(formatted.ends_with(&format!(": level {}", DEPTH)));
+        }
+    }
+
+    #[test]
+    fn debug_output() {
+        let style = Style::tree().debug(DebugOutput::Pretty);
+        let formatted = Nested(1).chain_with(style).to_string();
+        assert_eq!(
+            formatted,
+            concat!(
+                "level 1\n",
+                "  Nested(\n",
+                "      1,\n",
+                "  )\n",
+                "└─ level 0\n",
+                "     Nested(\n",
+                "         0,\n",
+                "     )",
+            )
+        );
+
+        let style = Style::numbered().debug(DebugOutput::Compact).max_depth(0);
+        let formatted = Nested(1).chain_with(style).to_string();
+        assert_eq!(
+            formatted,
+            "0: level 1\n     Nested(1)\n   ... 1 more cause omitted"
+        );
+
+        let style = Style::tree().debug(DebugOutput::Pretty).separator("\r\n");
+        let formatted = Nested(0).chain_with(style).to_string();
+        assert_eq!(formatted, "level 0\r\n  Nested(\r\n      0,\r\n  )");
+
+        let style = Style::single_line().debug(DebugOutput::Pretty);
+        let formatted = Nested(1).chain_with(style).to_string();
+        assert_eq!(formatted, "level 1 (Nested(1)): level 0 (Nested(0))");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn chain_report() {
+        let report = ChainReport::from(Nested(1));
+        assert_eq!(format!("{:?}", report), "level 1\nCaused by:\n  -> level 0");
+        assert_eq!(format!("{:#?}", report), "level 1: level 0");
+
+        let report = report.with_style(Style::numbered());
+        assert_eq!(format!("{}", report), "0: level 1\n1: level 0");
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(Nested(3).root_cause().to_string(), "level 0");
+        assert_eq!(Cyclic.root_cause().to_string(), "cyclic");
+        assert!(Nested(3).any_source(|error| error.to_string() == "level 1"));
+        assert!(!Nested(3).any_source(|error| error.to_string() == "level 4"));
+
+        assert!(CyclicSource.find_source::<Cyclic>().is_some());
+        assert!(Cyclic.find_source::<Nested>().is_none());
+        assert_eq!(Nested(3).find_source::<Nested>().unwrap().0, 3);
+
+        let error: &(dyn Error + 'static) = &Nested(3);
+        assert_eq!(error.find_source::<Nested>().unwrap().0, 3);
+        assert_eq!(error.root_cause().to_string(), "level 0");
+        assert_eq!(error.chain().to_string(), Nested(3).chain().to_string());
+
+        let error: Box<dyn Error + Send + Sync> = Box::new(CyclicSource);
+        assert!(error.find_source::<Cyclic>().is_some());
+        assert!(error.find_source::<Nested>().is_none());
+        assert_eq!(error.sources().count(), 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned_chain() {
+        let owned = OwnedErrorChain::new(&Nested(2));
+        assert_eq!(owned.message(), "level 2");
+        assert_eq!(owned.sources().count(), 3);
+        assert_eq!(owned.chain().to_string(), Nested(2).chain().to_string());
+
+        let owned = OwnedErrorChain::new(&CyclicSource);
+        assert_eq!(owned.chain().to_string(), CyclicSource.chain().to_string());
+
+        // The snapshot starts from the error itself rather than from a
+        // reference to it.
+        let owned = OwnedErrorChain::new(&Cyclic);
+        let messages: Vec<_> = owned.sources().map(|error| error.to_string()).collect();
+        let expected: Vec<_> = Cyclic.sources().map(|error| error.to_string()).collect();
+        assert_eq!(messages[..messages.len() - 1], expected);
+        assert_eq!(messages.last().unwrap(), "<cycle detected>");
+
+        fn assert_traits<T: Clone + Send + Sync + 'static>(_: &T) {}
+        assert_traits(&owned);
+
+        let owned = OwnedErrorChain::new(&Nested(DEPTH));
+        let cloned = owned.clone();
+        assert_eq!(owned, cloned);
+        assert!(owned > OwnedErrorChain::new(&Nested(DEPTH - 1)));
+        assert_eq!(cloned.sources().count(), DEPTH + 1);
+        assert_eq!(
+            format!("{:?}", owned),
+            r#"OwnedErrorChain { message: "level 20000", .. }"#
+        );
+    }
+
+    #[test]
+    fn numbered_single_error() {
+        let formatted = Nested(0).chain_with(Style::numbered()).to_string();
+        assert_eq!(formatted, "0: level 0");
     }
 }
@@ -0,0 +1,176 @@
+/// Logs an error chain with the given level, optionally prefixed with a
+/// message.
+///
+/// Accepts the same arguments as [`log::log!`], with the error preceding the
+/// message:
+///
+/// ```rust
+/// use display_error_chain::log_chain;
+/// use log::Level;
+///
+/// # let path = "config.toml";
+/// # let error = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file");
+/// // Logs "failed to read config.toml: No such file".
+/// log_chain!(Level::Warn, error, "failed to read {}", path);
+/// // Logs "No such file".
+/// log_chain!(target: "config", Level::Warn, error);
+/// ```
+///
+/// The [`error_chain!`][crate::error_chain], [`warn_chain!`][crate::warn_chain],
+/// [`info_chain!`][crate::info_chain], [`debug_chain!`][crate::debug_chain] and
+/// [`trace_chain!`][crate::trace_chain] macros are shortcuts for the
+/// corresponding levels.
+#[macro_export]
+macro_rules! log_chain {
+    (target: $target:expr, $level:expr, $error:expr $(,)?) => {
+        $crate::__private::log::log!(
+            target: $target,
+            $level,
+            "{}",
+            $crate::ErrorChainExt::chain(&$error)
+        )
+    };
+    (target: $target:expr, $level:expr, $error:expr, $($arg:tt)+) => {
+        $crate::__private::log::log!(
+            target: $target,
+            $level,
+            "{}: {}",
+            ::core::format_args!($($arg)+),
+            $crate::ErrorChainExt::chain(&$error)
+        )
+    };
+    ($level:expr, $error:expr $(,)?) => {
+        $crate::__private::log::log!($level, "{}", $crate::ErrorChainExt::chain(&$error))
+    };
+    ($level:expr, $error:expr, $($arg:tt)+) => {
+        $crate::__private::log::log!(
+            $level,
+            "{}: {}",
+            ::core::format_args!($($arg)+),
+            $crate::ErrorChainExt::chain(&$error)
+        )
+    };
+}
+
+/// Logs an error chain with the [error][log::Level::Error] level, see
+/// [`log_chain!`].
+#[macro_export]
+macro_rules! error_chain {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::log_chain!(target: $target, $crate::__private::log::Level::Error, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::log_chain!($crate::__private::log::Level::Error, $($arg)+)
+    };
+}
+
+/// Logs an error chain with the [warn][log::Level::Warn] level, see
+/// [`log_chain!`].
+#[macro_export]
+macro_rules! warn_chain {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::log_chain!(target: $target, $crate::__private::log::Level::Warn, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::log_chain!($crate::__private::log::Level::Warn, $($arg)+)
+    };
+}
+
+/// Logs an error chain with the [info][log::Level::Info] level, see
+/// [`log_chain!`].
+#[macro_export]
+macro_rules! info_chain {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::log_chain!(target: $target, $crate::__private::log::Level::Info, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::log_chain!($crate::__private::log::Level::Info, $($arg)+)
+    };
+}
+
+/// Logs an error chain with the [debug][log::Level::Debug] level, see
+/// [`log_chain!`].
+#[macro_export]
+macro_rules! debug_chain {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::log_chain!(target: $target, $crate::__private::log::Level::Debug, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::log_chain!($crate::__private::log::Level::Debug, $($arg)+)
+    };
+}
+
+/// Logs an error chain with the [trace][log::Level::Trace] level, see
+/// [`log_chain!`].
+#[macro_export]
+macro_rules! trace_chain {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::log_chain!(target: $target, $crate::__private::log::Level::Trace, $($arg)+)
+    };
+    ($($arg:tt)+) => {
+        $crate::log_chain!($crate::__private::log::Level::Trace, $($arg)+)
+    };
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use std::{io, sync::Mutex};
+
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+
+    use crate::{fixtures::Wrapper, ResultExt as _};
+
+    /// Collects the logged records.
+    struct Collector(Mutex<Vec<(Level, String, String)>>);
+
+    impl Log for Collector {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            self.0.lock().unwrap().push((
+                record.level(),
+                record.target().to_owned(),
+                record.args().to_string(),
+            ));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static COLLECTOR: Collector = Collector(Mutex::new(Vec::new()));
+
+    #[test]
+    fn logs_chains() {
+        log::set_logger(&COLLECTOR).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+
+        let error = Wrapper(io::Error::other("oops"));
+        error_chain!(error);
+        warn_chain!(target: "custom", error, "failed to do {}", "things");
+        log_chain!(Level::Info, &error, "done");
+
+        let result = Err::<(), _>(Wrapper(io::Error::other("oops")));
+        let result = result.log_err_chain(Level::Debug);
+        assert!(result.is_err());
+        assert_eq!(result.ok_or_log_chain(), None);
+        assert_eq!(Ok::<_, Wrapper>(1).ok_or_log_chain(), Some(1));
+
+        let chain = "wrapper\nCaused by:\n  -> oops";
+        let module = module_path!();
+        let expected = [
+            (Level::Error, module, chain.to_owned()),
+            (
+                Level::Warn,
+                "custom",
+                format!("failed to do things: {}", chain),
+            ),
+            (Level::Info, module, format!("done: {}", chain)),
+            (Level::Debug, "display_error_chain", chain.to_owned()),
+            (Level::Error, "display_error_chain", chain.to_owned()),
+        ]
+        .map(|(level, target, message)| (level, target.to_owned(), message));
+        assert_eq!(*COLLECTOR.0.lock().unwrap(), expected);
+    }
+}
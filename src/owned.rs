@@ -0,0 +1,163 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{cmp::Ordering, error::Error, fmt, hash, iter};
+
+use crate::{frames::Frame, ErrorChainExt};
+
+/// An owned snapshot of an error chain.
+///
+/// Captures the message of every error of a chain, so the chain can outlive
+/// the original error, be sent to another thread or be stored for later. The
+/// snapshot implements [`Error`] with the [sources][Error::source] mirroring
+/// the original chain, so it is formatted by a
+/// [`DisplayErrorChain`][crate::DisplayErrorChain] just like the original
+/// error.
+///
+/// ```rust
+/// use display_error_chain::{ErrorChainExt as _, OwnedErrorChain};
+///
+/// # #[derive(Debug)]
+/// # struct ConfigError(std::io::Error);
+/// # impl std::fmt::Display for ConfigError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         write!(f, "failed to read the config")
+/// #     }
+/// # }
+/// # impl std::error::Error for ConfigError {
+/// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+/// #         Some(&self.0)
+/// #     }
+/// # }
+/// let error = ConfigError(std::io::Error::new(
+///     std::io::ErrorKind::NotFound,
+///     "no such file",
+/// ));
+/// let owned = OwnedErrorChain::new(&error);
+///
+/// let formatted = std::thread::spawn(move || owned.into_chain().to_string())
+///     .join()
+///     .unwrap();
+/// assert_eq!(formatted, error.chain().to_string());
+/// ```
+///
+/// If the original chain contains a cycle, the snapshot ends with an error
+/// with the same `<cycle detected>` message that is displayed in place of
+/// the cycle. Since it is an error of its own, it is formatted as one, e.g.
+/// the [numbered][crate::Layout::Numbered] layout assigns a number to it.
+///
+/// Dropping, cloning, comparing and hashing a snapshot don't recurse into its
+/// sources, so even very deep chains can't overflow the stack. For the same
+/// reason, the [`Debug`][fmt::Debug] representation only includes the
+/// message of the top-level error.
+pub struct OwnedErrorChain {
+    message: String,
+    source: Option<Box<OwnedErrorChain>>,
+}
+
+impl OwnedErrorChain {
+    /// Captures the messages of the error and its sources.
+    pub fn new<E>(error: &E) -> Self
+    where
+        E: ErrorChainExt + ?Sized,
+    {
+        let chain = error.sources();
+        let cycle = chain.is_cyclic().then(|| Frame::Cycle.to_string());
+        let messages = chain.map(|error| error.to_string()).chain(cycle).collect();
+        Self::from_messages(messages)
+    }
+
+    /// Builds the chain from the messages, starting from the top-level
+    /// error.
+    pub(crate) fn from_messages(mut messages: Vec<String>) -> Self {
+        let mut owned = OwnedErrorChain {
+            message: messages.pop().unwrap_or_default(),
+            source: None,
+        };
+        while let Some(message) = messages.pop() {
+            owned = OwnedErrorChain {
+                message,
+                source: Some(Box::new(owned)),
+            };
+        }
+        owned
+    }
+
+    /// Returns the message of the top-level error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Iterates over the messages of the error and its sources.
+    fn messages(&self) -> impl Iterator<Item = &str> {
+        iter::successors(Some(self), |owned| owned.source.as_deref())
+            .map(|owned| owned.message.as_str())
+    }
+}
+
+impl Drop for OwnedErrorChain {
+    fn drop(&mut self) {
+        // Unlinks the sources one by one, so every one of them is dropped
+        // without any sources of its own.
+        let mut source = self.source.take();
+        while let Some(mut owned) = source {
+            source = owned.source.take();
+        }
+    }
+}
+
+impl fmt::Debug for OwnedErrorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedErrorChain")
+            .field("message", &self.message)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Clone for OwnedErrorChain {
+    fn clone(&self) -> Self {
+        Self::from_messages(self.messages().map(String::from).collect())
+    }
+}
+
+impl PartialEq for OwnedErrorChain {
+    fn eq(&self, other: &Self) -> bool {
+        self.messages().eq(other.messages())
+    }
+}
+
+impl Eq for OwnedErrorChain {}
+
+impl PartialOrd for OwnedErrorChain {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OwnedErrorChain {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.messages().cmp(other.messages())
+    }
+}
+
+impl hash::Hash for OwnedErrorChain {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        for message in self.messages() {
+            message.hash(state);
+        }
+    }
+}
+
+impl fmt::Display for OwnedErrorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for OwnedErrorChain {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|source| source as _)
+    }
+}
@@ -0,0 +1,254 @@
+use core::{
+    error::Error,
+    fmt::{self, Write as _},
+};
+
+#[cfg(feature = "alloc")]
+use crate::wrap::Wrapped;
+use crate::{
+    color::Palette,
+    frames::{Frame, Frames},
+    indent::{display_width, Indented, Joined},
+    DebugOutput, Layout, Style,
+};
+
+/// Formats the error and its sources using the given style and layout.
+pub(crate) fn render(
+    error: &dyn Error,
+    style: &Style,
+    layout: Layout,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let frames = Frames::new(error, style);
+    let palette = Palette::new(style);
+    if style.reverse {
+        render_frames(frames.reversed(), style, palette, layout, f)
+    } else {
+        render_frames(frames, style, palette, layout, f)
+    }
+}
+
+/// Formats the frames using the given style and layout.
+fn render_frames<'a>(
+    frames: impl Iterator<Item = Frame<'a>> + Clone,
+    style: &Style,
+    palette: Palette,
+    layout: Layout,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    match layout {
+        Layout::Multiline => multiline(frames, style, palette, f),
+        Layout::SingleLine => single_line(frames, style, palette, f),
+        Layout::Numbered => numbered(frames, style, palette, f),
+        Layout::Tree => tree(frames, style, palette, f),
+    }
+}
+
+/// Formats the sources as a list, one per line.
+///
+/// Continuation lines of multi-line messages are aligned with the first line
+/// of the message.
+fn multiline<'a>(
+    mut frames: impl Iterator<Item = Frame<'a>> + Clone,
+    style: &Style,
+    palette: Palette,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let Style {
+        prefix,
+        separator,
+        indent,
+        ..
+    } = *style;
+    let header = style.effective_header();
+
+    if let Some(top) = frames.next() {
+        write_message(f, style, "", 0, palette.headline(top))?;
+        write_debug(f, style, "", 0, top)?;
+    }
+
+    let mut frames = frames.peekable();
+    if frames.peek().is_some() && !header.is_empty() {
+        write!(f, "{}{}", separator, palette.header(header))?;
+    }
+    let width = display_width(prefix);
+    for frame in frames {
+        write!(f, "{}{}", separator, indent)?;
+        match frame {
+            Frame::Error(_) | Frame::Cycle => write!(f, "{}", palette.guide(prefix))?,
+            Frame::Omitted(_) => write!(f, "{:width$}", "", width = width)?,
+        }
+        let column = display_width(indent) + width;
+        write_message(f, style, "", column, frame)?;
+        write_debug(f, style, "", column, frame)?;
+    }
+    Ok(())
+}
+
+/// Formats the error and its sources on the same line.
+///
+/// Line breaks inside the messages are replaced with spaces, so the output
+/// always fits on a single line.
+fn single_line<'a>(
+    frames: impl Iterator<Item = Frame<'a>> + Clone,
+    style: &Style,
+    palette: Palette,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    for (idx, frame) in frames.enumerate() {
+        if idx == 0 {
+            write!(Joined::new(f), "{}", palette.headline(frame))?;
+        } else {
+            f.write_str(style.single_line_separator)?;
+            write!(Joined::new(f), "{}", frame)?;
+        }
+        if let (Frame::Error(error), DebugOutput::Compact | DebugOutput::Pretty) =
+            (frame, style.debug)
+        {
+            write!(Joined::new(f), " ({:?})", error)?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats the error and its sources as a numbered list.
+fn numbered<'a>(
+    frames: impl Iterator<Item = Frame<'a>> + Clone,
+    style: &Style,
+    palette: Palette,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let last_index = frames
+        .clone()
+        .filter(|frame| matches!(frame, Frame::Error(_)))
+        .count()
+        .saturating_sub(1);
+    let width = last_index
+        .checked_ilog10()
+        .map_or(1, |digits| digits as usize + 1);
+
+    let mut index = 0;
+    for (idx, frame) in frames.enumerate() {
+        if idx != 0 {
+            f.write_str(style.separator)?;
+        }
+        match frame {
+            Frame::Error(_) => {
+                // The displayed errors are numbered one after another
+                // starting from the top-level one, regardless of the order.
+                // The redundant errors are not displayed, so they don't get
+                // numbers either.
+                let number = if style.reverse {
+                    last_index - index
+                } else {
+                    index
+                };
+                index += 1;
+                write!(
+                    f,
+                    "{} ",
+                    palette.guide(format_args!("{:>width$}:", number, width = width))
+                )?
+            }
+            Frame::Omitted(_) | Frame::Cycle => write!(f, "{:width$}", "", width = width + 2)?,
+        }
+        if idx == 0 {
+            write_message(f, style, "", width + 2, palette.headline(frame))?;
+        } else {
+            write_message(f, style, "", width + 2, frame)?;
+        }
+        write_debug(f, style, "", width + 2, frame)?;
+    }
+    Ok(())
+}
+
+/// Formats the sources as a tree.
+///
+/// Continuation lines of multi-line messages are aligned with the first line
+/// of the message, keeping the tree guides intact.
+fn tree<'a>(
+    mut frames: impl Iterator<Item = Frame<'a>> + Clone,
+    style: &Style,
+    palette: Palette,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let (branch, last_branch, guide) = if style.ascii {
+        ("|- ", "`- ", "|")
+    } else {
+        ("├─ ", "└─ ", "│")
+    };
+
+    if let Some(top) = frames.next() {
+        write_message(f, style, "", 0, palette.headline(top))?;
+        write_debug(f, style, "", 0, top)?;
+    }
+
+    let mut frames = frames.peekable();
+    while let Some(frame) = frames.next() {
+        let (branch, guide) = if frames.peek().is_some() {
+            (branch, guide)
+        } else {
+            (last_branch, "")
+        };
+        write!(f, "{}{}", style.separator, palette.guide(branch))?;
+        write_message(f, style, guide, display_width(branch), frame)?;
+        write_debug(f, style, guide, display_width(branch), frame)?;
+    }
+    Ok(())
+}
+
+/// Writes a message which starts at the given column.
+///
+/// Continuation lines of the message are separated with the style's separator
+/// and indented with the `guide` followed by spaces up to the column, and long
+/// lines are wrapped if the style requires so.
+fn write_message(
+    f: &mut fmt::Formatter<'_>,
+    style: &Style,
+    guide: &str,
+    column: usize,
+    message: impl fmt::Display,
+) -> fmt::Result {
+    let mut indented = Indented::new(f, style.separator, guide, column - display_width(guide));
+    #[cfg(feature = "alloc")]
+    if let Some(width) = style.wrap {
+        let mut wrapped = Wrapped::new(&mut indented, width.saturating_sub(column));
+        write!(wrapped, "{}", message)?;
+        return wrapped.finish();
+    }
+    #[cfg(not(feature = "alloc"))]
+    let _ = style;
+    write!(indented, "{}", message)
+}
+
+/// Writes the debug representation of an error on the lines following its
+/// message, if the style requires so.
+///
+/// The debug representation is indented relative to the message by the
+/// style's indentation.
+fn write_debug(
+    f: &mut fmt::Formatter<'_>,
+    style: &Style,
+    guide: &str,
+    column: usize,
+    frame: Frame<'_>,
+) -> fmt::Result {
+    let Frame::Error(error) = frame else {
+        return Ok(());
+    };
+    // The line breaks are replaced with the separator by the adapter.
+    let mut indented = Indented::new(f, style.separator, guide, column - display_width(guide));
+    match style.debug {
+        DebugOutput::Hidden => Ok(()),
+        DebugOutput::Compact => write!(indented, "\n{}{:?}", style.indent, error),
+        DebugOutput::Pretty => {
+            write!(indented, "\n{}", style.indent)?;
+            let width = display_width(style.indent);
+            write!(
+                Indented::new(&mut indented, "\n", "", width),
+                "{:#?}",
+                error
+            )
+        }
+    }
+}
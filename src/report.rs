@@ -0,0 +1,268 @@
+use std::{
+    error::Error,
+    fmt, io,
+    process::{ExitCode, Termination},
+};
+
+use crate::{chain::static_sources, DisplayErrorChain, Style};
+
+/// An error wrapper which formats the whole chain with both
+/// [`Display`][fmt::Display] and [`Debug`][fmt::Debug].
+///
+/// When `main` returns an `Err(_)`, the standard library prints it out using
+/// the [`Debug`][fmt::Debug] implementation, so returning a [`ChainReport`]
+/// from `main` is enough to get the full chain printed. Any [`Error`] which is
+/// `Send + Sync + 'static` can be converted into a [`ChainReport`], so the `?`
+/// operator just works:
+///
+/// ```rust,no_run
+/// use display_error_chain::ChainReport;
+///
+/// # #[derive(Debug)]
+/// # struct ConfigError(std::io::Error);
+/// # impl std::fmt::Display for ConfigError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         write!(f, "failed to read the config")
+/// #     }
+/// # }
+/// # impl std::error::Error for ConfigError {
+/// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+/// #         Some(&self.0)
+/// #     }
+/// # }
+/// fn read_config() -> Result<String, ConfigError> {
+///     std::fs::read_to_string("/definitely/missing.toml").map_err(ConfigError)
+/// }
+///
+/// fn main() -> Result<(), ChainReport> {
+///     let config = read_config()?;
+///     println!("{config}");
+///     Ok(())
+/// }
+/// ```
+///
+/// Prints out the following before exiting:
+///
+/// ```text
+/// Error: failed to read the config
+/// Caused by:
+///   -> No such file or directory (os error 2)
+/// ```
+///
+/// [`ChainReport`] deliberately doesn't implement [`Error`], since otherwise
+/// the conversion from an arbitrary [`Error`] would be impossible.
+pub struct ChainReport {
+    error: Box<dyn Error + Send + Sync + 'static>,
+    style: Style,
+}
+
+impl ChainReport {
+    /// Wraps the error.
+    pub fn new<E>(error: E) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        ChainReport {
+            error: Box::new(error),
+            style: Style::new(),
+        }
+    }
+
+    /// Replaces the [`Style`] the error chain is formatted with.
+    pub fn with_style(self, style: Style) -> Self {
+        ChainReport { style, ..self }
+    }
+
+    /// Returns the [`Style`] the error chain is formatted with.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> Box<dyn Error + Send + Sync + 'static> {
+        self.error
+    }
+
+    /// Borrows the error as a [`DisplayErrorChain`].
+    pub fn chain(&self) -> DisplayErrorChain<&(dyn Error + Send + Sync + 'static)> {
+        DisplayErrorChain::new(&*self.error).with_style(self.style)
+    }
+}
+
+impl<E> From<E> for ChainReport
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn from(error: E) -> Self {
+        ChainReport::new(error)
+    }
+}
+
+impl fmt::Display for ChainReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.chain(), f)
+    }
+}
+
+impl fmt::Debug for ChainReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.chain(), f)
+    }
+}
+
+/// The outcome of a program, which prints out the error chain to the standard
+/// error stream and exits with a meaningful code when returned from `main`.
+///
+/// The exit code is [`ExitCode::FAILURE`] by default, but it can be derived
+/// from the errors of the chain with [`Report::with_exit_code`]: the
+/// errors are checked starting from the top-level one, and the first mapped
+/// code is used.
+///
+/// ```rust,no_run
+/// use display_error_chain::{ChainReport, Report};
+///
+/// fn run() -> Result<(), ChainReport> {
+///     let config = std::fs::read_to_string("/etc/app.toml")?;
+///     println!("{config}");
+///     Ok(())
+/// }
+///
+/// fn main() -> Report {
+///     // Exits with `EX_NOINPUT` (66) if the config file is missing.
+///     Report::from(run()).with_exit_code(Report::sysexits)
+/// }
+/// ```
+pub struct Report {
+    result: Result<(), ChainReport>,
+    exit_code: Box<ExitCodeFn>,
+}
+
+/// Maps an error of the chain into an exit code.
+type ExitCodeFn = dyn Fn(&(dyn Error + 'static)) -> Option<ExitCode>;
+
+impl Report {
+    /// Wraps the outcome of a program.
+    pub fn new<E>(result: Result<(), E>) -> Self
+    where
+        E: Into<ChainReport>,
+    {
+        Report {
+            result: result.map_err(Into::into),
+            exit_code: Box::new(|_| None),
+        }
+    }
+
+    /// Replaces the [`Style`] the error chain is formatted with.
+    pub fn with_style(self, style: Style) -> Self {
+        Report {
+            result: self.result.map_err(|report| report.with_style(style)),
+            ..self
+        }
+    }
+
+    /// Sets the function which maps an error of the chain into an exit code.
+    pub fn with_exit_code<F>(self, exit_code: F) -> Self
+    where
+        F: Fn(&(dyn Error + 'static)) -> Option<ExitCode> + 'static,
+    {
+        Report {
+            exit_code: Box::new(exit_code),
+            ..self
+        }
+    }
+
+    /// Maps [`io::Error`]s into the exit codes defined by `sysexits.h`.
+    ///
+    /// | [`io::ErrorKind`] | Exit code |
+    /// |---|---|
+    /// | `NotFound` | `EX_NOINPUT` (66) |
+    /// | `PermissionDenied` | `EX_NOPERM` (77) |
+    /// | `AlreadyExists` | `EX_CANTCREAT` (73) |
+    /// | `InvalidData` | `EX_DATAERR` (65) |
+    /// | `ConnectionRefused`, `ConnectionReset`, `ConnectionAborted`, `NotConnected`, `AddrInUse`, `AddrNotAvailable`, `HostUnreachable`, `NetworkUnreachable` | `EX_UNAVAILABLE` (69) |
+    /// | `TimedOut`, `WouldBlock`, `Interrupted` | `EX_TEMPFAIL` (75) |
+    /// | `OutOfMemory` | `EX_OSERR` (71) |
+    /// | Any other kind | `EX_IOERR` (74) |
+    ///
+    /// Other errors are not mapped.
+    pub fn sysexits(error: &(dyn Error + 'static)) -> Option<ExitCode> {
+        use io::ErrorKind::*;
+
+        let code = match error.downcast_ref::<io::Error>()?.kind() {
+            NotFound => 66,
+            PermissionDenied => 77,
+            AlreadyExists => 73,
+            InvalidData => 65,
+            ConnectionRefused | ConnectionReset | ConnectionAborted | NotConnected | AddrInUse
+            | AddrNotAvailable | HostUnreachable | NetworkUnreachable => 69,
+            TimedOut | WouldBlock | Interrupted => 75,
+            OutOfMemory => 71,
+            _ => 74,
+        };
+        Some(ExitCode::from(code))
+    }
+
+    /// Calculates the exit code of the program.
+    fn exit_code(&self) -> ExitCode {
+        let Err(report) = &self.result else {
+            return ExitCode::SUCCESS;
+        };
+        static_sources(&*report.error)
+            .find_map(|error| (self.exit_code)(error))
+            .unwrap_or(ExitCode::FAILURE)
+    }
+}
+
+impl<E> From<Result<(), E>> for Report
+where
+    E: Into<ChainReport>,
+{
+    fn from(result: Result<(), E>) -> Self {
+        Report::new(result)
+    }
+}
+
+impl Termination for Report {
+    fn report(self) -> ExitCode {
+        let exit_code = self.exit_code();
+        if let Err(report) = &self.result {
+            eprintln!("Error: {}", report);
+        }
+        exit_code
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChainReport, Report};
+    use crate::fixtures::Wrapper;
+    use std::{io, process::ExitCode};
+
+    #[test]
+    fn exit_codes() {
+        let report = Report::new(Ok::<_, ChainReport>(()));
+        assert_eq!(report.exit_code(), ExitCode::SUCCESS);
+
+        let error = || Wrapper(io::Error::from(io::ErrorKind::PermissionDenied));
+        let report = Report::new(Err(error()));
+        assert_eq!(report.exit_code(), ExitCode::FAILURE);
+
+        let report = Report::new(Err(error())).with_exit_code(Report::sysexits);
+        assert_eq!(report.exit_code(), ExitCode::from(77));
+
+        let report = Report::new(Err(error()))
+            .with_exit_code(|error| error.downcast_ref::<Wrapper>().map(|_| ExitCode::from(42)));
+        assert_eq!(report.exit_code(), ExitCode::from(42));
+
+        let codes = [
+            (io::ErrorKind::NotFound, 3),
+            (io::ErrorKind::PermissionDenied, 4),
+        ];
+        let report = Report::new(Err(error())).with_exit_code(move |error| {
+            let kind = error.downcast_ref::<io::Error>()?.kind();
+            let &(_, code) = codes.iter().find(|&&(known, _)| known == kind)?;
+            Some(ExitCode::from(code))
+        });
+        assert_eq!(report.exit_code(), ExitCode::from(4));
+    }
+}
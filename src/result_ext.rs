@@ -1,9 +1,21 @@
-use std::{error::Error, fmt};
+use std::fmt;
 
-use crate::DisplayErrorChain;
+#[cfg(feature = "log")]
+use std::{error::Error, panic::Location};
+
+use crate::{DisplayErrorChain, IntoError};
 
 /// A helper extension trait to "unwrap" results with an error chain.
-pub trait ResultExt<T, E> {
+///
+/// Besides the results with an [`Error`][std::error::Error], it's implemented for the results
+/// with any other error type which implements [`IntoError`], e.g.
+/// `Box<dyn Error + Send + Sync>`. In that case `E` is a marker type of the
+/// [`IntoError`] implementation rather than the error type itself.
+///
+/// The trait is sealed and can't be implemented outside of this crate, so new
+/// methods can be added to it, e.g. by the `log` feature, without breaking
+/// anyone.
+pub trait ResultExt<T, E: ?Sized>: sealed::Sealed {
     /// Like [`Result::unwrap`][Result::unwrap], but wraps the error in the
     /// [DisplayErrorChain] and prints out the full chain in case an `Err(_)`
     /// value is encountered.
@@ -15,9 +27,33 @@ pub trait ResultExt<T, E> {
     /// value is encountered.
     #[track_caller]
     fn expect_chain(self, msg: &str) -> T;
+
+    /// Logs the full chain with the given level in case an `Err(_)` value is
+    /// encountered, and returns the result as is.
+    ///
+    /// The record is attributed to the location of the caller, but its target
+    /// is always `display_error_chain`. Use the [`log_chain!`][crate::log_chain]
+    /// macro to log with the target of the calling module.
+    #[cfg(feature = "log")]
+    #[track_caller]
+    fn log_err_chain(self, level: log::Level) -> Self;
+
+    /// Like [`Result::ok`][Result::ok], but logs the full chain with the
+    /// [error][log::Level::Error] level in case an `Err(_)` value is
+    /// encountered.
+    ///
+    /// The record is attributed to the location of the caller, but its target
+    /// is always `display_error_chain`.
+    #[cfg(feature = "log")]
+    #[track_caller]
+    fn ok_or_log_chain(self) -> Option<T>;
 }
 
-impl<T, E: Error> ResultExt<T, E> for Result<T, E> {
+impl<T, E, M> ResultExt<T, M> for Result<T, E>
+where
+    E: IntoError<M>,
+    M: ?Sized,
+{
     #[inline]
     #[track_caller]
     fn unwrap_chain(self) -> T {
@@ -25,7 +61,7 @@ impl<T, E: Error> ResultExt<T, E> for Result<T, E> {
             Ok(value) => value,
             Err(e) => unwrap_failed(
                 "called `Result::unwrap_chain()` on an `Err` value",
-                &DisplayErrorChain::new(&e),
+                &DisplayErrorChain::new(e.as_error()),
             ),
         }
     }
@@ -34,9 +70,32 @@ impl<T, E: Error> ResultExt<T, E> for Result<T, E> {
     fn expect_chain(self, msg: &str) -> T {
         match self {
             Ok(value) => value,
-            Err(e) => unwrap_failed(msg, &DisplayErrorChain::new(&e)),
+            Err(e) => unwrap_failed(msg, &DisplayErrorChain::new(e.as_error())),
         }
     }
+    #[cfg(feature = "log")]
+    #[inline]
+    #[track_caller]
+    fn log_err_chain(self, level: log::Level) -> Self {
+        if let Err(e) = &self {
+            log_failed(level, e.as_error());
+        }
+        self
+    }
+    #[cfg(feature = "log")]
+    #[inline]
+    #[track_caller]
+    fn ok_or_log_chain(self) -> Option<T> {
+        self.log_err_chain(log::Level::Error).ok()
+    }
+}
+
+mod sealed {
+    /// Prevents [`ResultExt`][super::ResultExt] from being implemented
+    /// outside of this crate.
+    pub trait Sealed {}
+
+    impl<T, E> Sealed for Result<T, E> {}
 }
 
 // This is a separate function to reduce the code size of the methods
@@ -47,6 +106,35 @@ fn unwrap_failed(msg: &str, error: &dyn fmt::Display) -> ! {
     panic!("{}: {}", msg, error)
 }
 
+/// The target of the records logged by the methods, since the module of the
+/// caller is unknown.
+#[cfg(feature = "log")]
+const LOG_TARGET: &str = "display_error_chain";
+
+// This is a separate function to reduce the code size of the methods
+#[cfg(feature = "log")]
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn log_failed(level: log::Level, error: &dyn Error) {
+    let location = Location::caller();
+    let metadata = log::Metadata::builder()
+        .level(level)
+        .target(LOG_TARGET)
+        .build();
+    if level > log::max_level() || !log::logger().enabled(&metadata) {
+        return;
+    }
+    log::logger().log(
+        &log::Record::builder()
+            .metadata(metadata)
+            .args(format_args!("{}", DisplayErrorChain::new(error)))
+            .file(Some(location.file()))
+            .line(Some(location.line()))
+            .build(),
+    );
+}
+
 #[cfg(test)]
 mod test {
     use super::ResultExt;
@@ -101,4 +189,25 @@ Caused by:
     fn test_expect() {
         Err::<(), _>(TopLevel).expect_chain("Some message");
     }
+
+    #[test]
+    #[should_panic(expected = "\
+    called `Result::unwrap_chain()` on an `Err` value: top level\n\
+Caused by:
+  -> mid level
+  -> low level")]
+    fn test_unwrap_boxed() {
+        Err::<(), Box<dyn ::std::error::Error + Send + Sync>>(Box::new(TopLevel)).unwrap_chain();
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    #[should_panic(expected = "\
+    Some message: top level\n\
+Caused by:
+  -> mid level
+  -> low level")]
+    fn test_expect_anyhow() {
+        Err::<(), _>(anyhow::Error::new(TopLevel)).expect_chain("Some message");
+    }
 }
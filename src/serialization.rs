@@ -0,0 +1,198 @@
+use alloc::{string::String, vec::Vec};
+use core::{error::Error, fmt};
+
+use serde::{
+    de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{frames::Frame, Chain, DisplayErrorChain, OwnedErrorChain};
+
+/// Serializes the chain as a sequence of frames, i.e.
+/// `[{"message": "top level"}, {"message": "mid level"}, ...]`.
+///
+/// The [`Style`][crate::Style] of the chain is not taken into account.
+impl<E> Serialize for DisplayErrorChain<E>
+where
+    E: Error,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_chain(self.iter(), serializer)
+    }
+}
+
+/// Serializes the chain as a sequence of frames, i.e.
+/// `[{"message": "top level"}, {"message": "mid level"}, ...]`.
+impl Serialize for OwnedErrorChain {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_chain(Chain::new(self), serializer)
+    }
+}
+
+/// Deserializes the chain from a non-empty sequence of frames, i.e.
+/// `[{"message": "top level"}, {"message": "mid level"}, ...]`.
+impl<'de> Deserialize<'de> for OwnedErrorChain {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(ChainVisitor)
+    }
+}
+
+/// Serializes the errors of the chain, marking a cycle the same way as
+/// [`OwnedErrorChain`] does.
+fn serialize_chain<S>(chain: Chain<'_>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let cycle = chain.is_cyclic().then_some(Frame::Cycle);
+    let mut seq = serializer.serialize_seq(Some(chain.len() + usize::from(cycle.is_some())))?;
+    for error in chain {
+        seq.serialize_element(&SerializedFrame(error))?;
+    }
+    if let Some(cycle) = cycle {
+        seq.serialize_element(&SerializedFrame(cycle))?;
+    }
+    seq.end()
+}
+
+/// A single frame of a serialized chain.
+struct SerializedFrame<T>(T);
+
+impl<T> Serialize for SerializedFrame<T>
+where
+    T: fmt::Display,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut frame = serializer.serialize_struct("Frame", 1)?;
+        frame.serialize_field("message", &SerializedMessage(&self.0))?;
+        frame.end()
+    }
+}
+
+/// A message which is serialized without being collected into a string.
+struct SerializedMessage<'a, T>(&'a T);
+
+impl<T> Serialize for SerializedMessage<'_, T>
+where
+    T: fmt::Display,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self.0)
+    }
+}
+
+/// Deserializes a chain from a sequence of frames.
+struct ChainVisitor;
+
+impl<'de> Visitor<'de> for ChainVisitor {
+    type Value = OwnedErrorChain;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a non-empty sequence of error frames")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut messages = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(DeserializedFrame(message)) = seq.next_element()? {
+            messages.push(message);
+        }
+        if messages.is_empty() {
+            return Err(de::Error::invalid_length(0, &self));
+        }
+        Ok(OwnedErrorChain::from_messages(messages))
+    }
+}
+
+/// The message of a single frame of a deserialized chain.
+struct DeserializedFrame(String);
+
+impl<'de> Deserialize<'de> for DeserializedFrame {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_struct("Frame", &["message"], FrameVisitor)
+            .map(DeserializedFrame)
+    }
+}
+
+/// Deserializes the message of a single frame.
+struct FrameVisitor;
+
+impl<'de> Visitor<'de> for FrameVisitor {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an error frame")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut message = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key != "message" {
+                map.next_value::<IgnoredAny>()?;
+            } else if message.is_some() {
+                return Err(de::Error::duplicate_field("message"));
+            } else {
+                message = Some(map.next_value()?);
+            }
+        }
+        message.ok_or_else(|| de::Error::missing_field("message"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{fixtures::Wrapper, ErrorChainExt as _, OwnedErrorChain};
+
+    #[test]
+    fn round_trip() {
+        let error = Wrapper(std::io::Error::other("oops"));
+        let json = serde_json::to_string(&error.chain()).unwrap();
+        assert_eq!(json, r#"[{"message":"wrapper"},{"message":"oops"}]"#);
+
+        let owned: OwnedErrorChain = serde_json::from_str(&json).unwrap();
+        assert_eq!(owned, OwnedErrorChain::new(&error));
+        assert_eq!(serde_json::to_string(&owned).unwrap(), json);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let json = r#"[{"message":"top","code":42},{"message":"low"}]"#;
+        let owned: OwnedErrorChain = serde_json::from_str(json).unwrap();
+        assert_eq!(owned.into_chain().to_string(), "top\nCaused by:\n  -> low");
+
+        assert!(serde_json::from_str::<OwnedErrorChain>("[]").is_err());
+        assert!(serde_json::from_str::<OwnedErrorChain>(r#"[{"code":42}]"#).is_err());
+    }
+}
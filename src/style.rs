@@ -0,0 +1,540 @@
+#[cfg(feature = "color")]
+use crate::ColorChoice;
+
+/// Describes how a [`DisplayErrorChain`][crate::DisplayErrorChain] lays out
+/// an error and its sources.
+///
+/// The default style produces the following output:
+///
+/// ```text
+/// top level
+/// Caused by:
+///   -> mid level
+///   -> low level
+/// ```
+///
+/// Every part of it can be adjusted with the builder methods:
+///
+/// ```rust
+/// use display_error_chain::{ErrorChainExt as _, Style};
+///
+/// # #[derive(Debug)]
+/// # struct ConfigError(std::io::Error);
+/// # impl std::fmt::Display for ConfigError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         write!(f, "failed to read the config")
+/// #     }
+/// # }
+/// # impl std::error::Error for ConfigError {
+/// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+/// #         Some(&self.0)
+/// #     }
+/// # }
+/// let error = ConfigError(std::io::Error::new(
+///     std::io::ErrorKind::NotFound,
+///     "no such file",
+/// ));
+///
+/// const HOUSE_STYLE: Style = Style::new().header("Because:").indent("").prefix("* ");
+/// let formatted = error.chain().with_style(HOUSE_STYLE).to_string();
+/// assert_eq!("failed to read the config\nBecause:\n* no such file", formatted);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Style {
+    pub(crate) layout: Layout,
+    pub(crate) header: Option<&'static str>,
+    pub(crate) prefix: &'static str,
+    pub(crate) separator: &'static str,
+    pub(crate) indent: &'static str,
+    pub(crate) single_line_separator: &'static str,
+    pub(crate) max_depth: Option<usize>,
+    #[cfg(feature = "alloc")]
+    pub(crate) deduplicate: bool,
+    pub(crate) ascii: bool,
+    pub(crate) reverse: bool,
+    #[cfg(feature = "alloc")]
+    pub(crate) wrap: Option<usize>,
+    pub(crate) debug: DebugOutput,
+    #[cfg(feature = "color")]
+    pub(crate) color: ColorChoice,
+}
+
+/// The overall shape of a formatted error chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Layout {
+    /// The top-level error followed by a header and a list of causes, one
+    /// per line:
+    ///
+    /// ```text
+    /// top level
+    /// Caused by:
+    ///   -> mid level
+    ///   -> low level
+    /// ```
+    #[default]
+    Multiline,
+    /// All the errors on a single line, joined with the
+    /// [single line separator][Style::single_line_separator]:
+    ///
+    /// ```text
+    /// top level: mid level: low level
+    /// ```
+    ///
+    /// Line breaks inside the messages are replaced with spaces.
+    SingleLine,
+    /// Every error on its own line, numbered from the top-level one:
+    ///
+    /// ```text
+    /// 0: top level
+    /// 1: mid level
+    /// 2: low level
+    /// ```
+    ///
+    /// The numbers are right-aligned, so the messages of long chains line up.
+    Numbered,
+    /// The top-level error followed by its sources drawn as a tree:
+    ///
+    /// ```text
+    /// top level
+    /// ├─ mid level
+    /// └─ low level
+    /// ```
+    ///
+    /// Only ASCII characters are used when the [`Style::ascii`] option is
+    /// set:
+    ///
+    /// ```text
+    /// top level
+    /// |- mid level
+    /// `- low level
+    /// ```
+    Tree,
+}
+
+/// Whether the [`Debug`][core::fmt::Debug] representation of every error is
+/// displayed along with its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum DebugOutput {
+    /// Only the messages are displayed.
+    #[default]
+    Hidden,
+    /// The `{:?}` representation is displayed under every message.
+    Compact,
+    /// The `{:#?}` representation is displayed under every message.
+    ///
+    /// The [single line][Layout::SingleLine] layout falls back to the
+    /// [compact][DebugOutput::Compact] representation.
+    Pretty,
+}
+
+impl Style {
+    /// The default header which is printed before the list of causes.
+    pub const DEFAULT_HEADER: &'static str = "Caused by:";
+
+    /// A header which suits the [reversed][Style::reverse] order.
+    pub const REVERSED_HEADER: &'static str = "The above error caused:";
+
+    /// The default prefix of every cause.
+    pub const DEFAULT_PREFIX: &'static str = "-> ";
+
+    /// The default separator between the lines of the output.
+    pub const DEFAULT_SEPARATOR: &'static str = "\n";
+
+    /// The default indentation of every cause.
+    pub const DEFAULT_INDENT: &'static str = "  ";
+
+    /// The default separator between the errors in the
+    /// [single line][Layout::SingleLine] layout.
+    pub const DEFAULT_SINGLE_LINE_SEPARATOR: &'static str = ": ";
+
+    /// Creates the default style.
+    pub const fn new() -> Self {
+        Style {
+            layout: Layout::Multiline,
+            header: None,
+            prefix: Self::DEFAULT_PREFIX,
+            separator: Self::DEFAULT_SEPARATOR,
+            indent: Self::DEFAULT_INDENT,
+            single_line_separator: Self::DEFAULT_SINGLE_LINE_SEPARATOR,
+            max_depth: None,
+            #[cfg(feature = "alloc")]
+            deduplicate: false,
+            ascii: false,
+            reverse: false,
+            #[cfg(feature = "alloc")]
+            wrap: None,
+            debug: DebugOutput::Hidden,
+            #[cfg(feature = "color")]
+            color: ColorChoice::Never,
+        }
+    }
+
+    /// Creates a style with the [single line][Layout::SingleLine] layout.
+    ///
+    /// ```rust
+    /// use display_error_chain::{ErrorChainExt as _, Style};
+    ///
+    /// # #[derive(Debug)]
+    /// # struct ConfigError(std::io::Error);
+    /// # impl std::fmt::Display for ConfigError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "failed to read the config")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for ConfigError {
+    /// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// #         Some(&self.0)
+    /// #     }
+    /// # }
+    /// let error = ConfigError(std::io::Error::new(
+    ///     std::io::ErrorKind::NotFound,
+    ///     "no such file",
+    /// ));
+    ///
+    /// let formatted = error.chain_with(Style::single_line()).to_string();
+    /// assert_eq!("failed to read the config: no such file", formatted);
+    ///
+    /// let formatted = error
+    ///     .chain_with(Style::single_line().single_line_separator(" <- "))
+    ///     .to_string();
+    /// assert_eq!("failed to read the config <- no such file", formatted);
+    /// ```
+    pub const fn single_line() -> Self {
+        Style::new().layout(Layout::SingleLine)
+    }
+
+    /// Sets the [`Layout`] of the output.
+    pub const fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the header which is printed before the list of causes in the
+    /// [multiline][Layout::Multiline] layout.
+    ///
+    /// An empty header is not printed at all, so the causes follow the
+    /// top-level error immediately.
+    ///
+    /// Unless a header is set explicitly, [`Style::DEFAULT_HEADER`] is used,
+    /// or [`Style::REVERSED_HEADER`] in the [reversed][Style::reverse] order.
+    pub const fn header(mut self, header: &'static str) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// Returns the header which is printed before the list of causes.
+    pub(crate) const fn effective_header(&self) -> &'static str {
+        match self.header {
+            Some(header) => header,
+            None if self.reverse => Self::REVERSED_HEADER,
+            None => Self::DEFAULT_HEADER,
+        }
+    }
+
+    /// Sets the prefix which is printed before every cause (right after the
+    /// [indentation][Style::indent]).
+    pub const fn prefix(mut self, prefix: &'static str) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Creates a style with the [numbered][Layout::Numbered] layout.
+    pub const fn numbered() -> Self {
+        Style::new().layout(Layout::Numbered)
+    }
+
+    /// Creates a style with the [tree][Layout::Tree] layout.
+    pub const fn tree() -> Self {
+        Style::new().layout(Layout::Tree)
+    }
+
+    /// Sets the separator which is printed between the lines of the output.
+    ///
+    /// The line breaks inside multi-line messages and debug representations
+    /// are replaced with it as well.
+    pub const fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the indentation of every cause.
+    pub const fn indent(mut self, indent: &'static str) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets the separator which is printed between the errors in the
+    /// [single line][Layout::SingleLine] layout.
+    pub const fn single_line_separator(mut self, separator: &'static str) -> Self {
+        self.single_line_separator = separator;
+        self
+    }
+
+    /// Limits the number of causes which are displayed.
+    ///
+    /// The sources beyond the limit are not displayed, but counted and
+    /// summarized instead:
+    ///
+    /// ```rust
+    /// use display_error_chain::{ErrorChainExt as _, Style};
+    ///
+    /// # #[derive(Debug)]
+    /// # struct Retry(u32);
+    /// # impl std::fmt::Display for Retry {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "attempt #{} failed", self.0)
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for Retry {
+    /// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// #         static ATTEMPTS: [Retry; 3] = [Retry(1), Retry(2), Retry(3)];
+    /// #         ATTEMPTS.get(self.0 as usize).map(|retry| retry as _)
+    /// #     }
+    /// # }
+    /// let formatted = Retry(0).chain_with(Style::new().max_depth(1)).to_string();
+    /// assert_eq!(
+    ///     formatted,
+    ///     "\
+    /// attempt #0 failed
+    /// Caused by:
+    ///   -> attempt #1 failed
+    ///      ... 2 more causes omitted"
+    /// );
+    /// ```
+    pub const fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Enables or disables skipping of the redundant causes.
+    ///
+    /// Many errors include the message of their source into their own one,
+    /// so the same text is displayed twice. When deduplication is enabled, a
+    /// cause is skipped if the message of its parent already contains the
+    /// cause's message (which includes the case of consecutive identical
+    /// messages):
+    ///
+    /// ```rust
+    /// use display_error_chain::{ErrorChainExt as _, Style};
+    ///
+    /// # #[derive(Debug)]
+    /// # struct ConfigError(std::io::Error);
+    /// # impl std::fmt::Display for ConfigError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "failed to open config: {}", self.0)
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for ConfigError {
+    /// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// #         Some(&self.0)
+    /// #     }
+    /// # }
+    /// // `ConfigError` includes the message of its source into its own one.
+    /// let error = ConfigError(std::io::Error::new(
+    ///     std::io::ErrorKind::NotFound,
+    ///     "No such file",
+    /// ));
+    ///
+    /// let formatted = error.chain_with(Style::new().deduplicate(true)).to_string();
+    /// assert_eq!("failed to open config: No such file", formatted);
+    /// ```
+    ///
+    /// The redundant errors are skipped altogether: the
+    /// [numbered][Layout::Numbered] layout doesn't assign numbers to them,
+    /// and they are not counted as omitted when the chain is
+    /// [limited][Style::max_depth].
+    ///
+    /// Please note that it requires every error of the chain to be formatted
+    /// twice, so it requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub const fn deduplicate(mut self, deduplicate: bool) -> Self {
+        self.deduplicate = deduplicate;
+        self
+    }
+
+    /// Restricts the output to ASCII characters, replacing the box-drawing
+    /// characters of the [tree][Layout::Tree] layout.
+    pub const fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Reverses the order of the chain, so the root cause is displayed first
+    /// and the top-level error is displayed last.
+    ///
+    /// Unless a [header][Style::header] is set explicitly, the causes are
+    /// preceded by [`Style::REVERSED_HEADER`]:
+    ///
+    /// ```rust
+    /// use display_error_chain::{ErrorChainExt as _, Style};
+    ///
+    /// # #[derive(Debug)]
+    /// # struct ConfigError(std::io::Error);
+    /// # impl std::fmt::Display for ConfigError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "failed to read the config")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for ConfigError {
+    /// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// #         Some(&self.0)
+    /// #     }
+    /// # }
+    /// let error = ConfigError(std::io::Error::new(
+    ///     std::io::ErrorKind::NotFound,
+    ///     "no such file",
+    /// ));
+    ///
+    /// let formatted = error.chain_with(Style::new().reverse(true)).to_string();
+    /// assert_eq!(
+    ///     formatted,
+    ///     "no such file\nThe above error caused:\n  -> failed to read the config"
+    /// );
+    /// ```
+    ///
+    /// The [numbered][Layout::Numbered] layout keeps numbering the errors
+    /// starting from the top-level one.
+    pub const fn reverse(mut self, reverse: bool) -> Self {
+        self.reverse = reverse;
+        self
+    }
+
+    /// Wraps the lines of the output which are longer than the given width.
+    ///
+    /// The lines are broken at spaces, and the wrapped lines are aligned with
+    /// the beginning of the message. Pass the width of the terminal to make
+    /// the output fit into it:
+    ///
+    /// ```rust
+    /// use display_error_chain::{ErrorChainExt as _, Style};
+    ///
+    /// # #[derive(Debug)]
+    /// # struct RequestError(std::io::Error);
+    /// # impl std::fmt::Display for RequestError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "request to https://example.com/api/v1/items failed")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for RequestError {
+    /// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// #         Some(&self.0)
+    /// #     }
+    /// # }
+    /// let error = RequestError(std::io::Error::new(
+    ///     std::io::ErrorKind::ConnectionRefused,
+    ///     "the server refused to accept the connection",
+    /// ));
+    ///
+    /// let formatted = error.chain_with(Style::new().wrap(30)).to_string();
+    /// assert_eq!(
+    ///     formatted,
+    ///     "\
+    /// request to
+    /// https://example.com/api/v1/items
+    /// failed
+    /// Caused by:
+    ///   -> the server refused to
+    ///      accept the connection"
+    /// );
+    /// ```
+    ///
+    /// The [single line][Layout::SingleLine] layout is never wrapped.
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub const fn wrap(mut self, width: usize) -> Self {
+        self.wrap = Some(width);
+        self
+    }
+
+    /// Selects whether the [`Debug`][core::fmt::Debug] representation of every
+    /// error is displayed along with its message.
+    ///
+    /// The representation is displayed under the message, indented by the
+    /// [indentation][Style::indent]:
+    ///
+    /// ```rust
+    /// use display_error_chain::{DebugOutput, ErrorChainExt as _, Style};
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError {
+    ///     path: &'static str,
+    ///     source: std::io::Error,
+    /// }
+    ///
+    /// # impl std::fmt::Display for ConfigError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "failed to read the config")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for ConfigError {
+    /// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// #         Some(&self.source)
+    /// #     }
+    /// # }
+    /// let error = ConfigError {
+    ///     path: "/etc/app.toml",
+    ///     source: std::io::Error::from(std::io::ErrorKind::NotFound),
+    /// };
+    ///
+    /// let formatted = error.chain_with(Style::new().debug(DebugOutput::Compact)).to_string();
+    /// assert_eq!(
+    ///     formatted,
+    ///     "\
+    /// failed to read the config
+    ///   ConfigError { path: \"/etc/app.toml\", source: Kind(NotFound) }
+    /// Caused by:
+    ///   -> entity not found
+    ///        Kind(NotFound)"
+    /// );
+    /// ```
+    pub const fn debug(mut self, debug: DebugOutput) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    /// Selects whether the output is colored with ANSI escape codes.
+    ///
+    /// When enabled, the first displayed error is bold red, the header is
+    /// dimmed, and the prefixes, numbers and tree guides are cyan:
+    ///
+    /// ```rust
+    /// use display_error_chain::{ColorChoice, ErrorChainExt as _, Style};
+    ///
+    /// # #[derive(Debug)]
+    /// # struct ConfigError(std::io::Error);
+    /// # impl std::fmt::Display for ConfigError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "failed to read the config")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for ConfigError {
+    /// #     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// #         Some(&self.0)
+    /// #     }
+    /// # }
+    /// let error = ConfigError(std::io::Error::new(
+    ///     std::io::ErrorKind::NotFound,
+    ///     "no such file",
+    /// ));
+    ///
+    /// let formatted = error.chain_with(Style::new().color(ColorChoice::Always)).to_string();
+    /// assert_eq!(
+    ///     formatted,
+    ///     "\x1b[1;31mfailed to read the config\x1b[0m\n\
+    ///      \x1b[2mCaused by:\x1b[0m\n  \
+    ///      \x1b[36m-> \x1b[0mno such file"
+    /// );
+    /// ```
+    #[cfg(feature = "color")]
+    pub const fn color(mut self, color: ColorChoice) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style::new()
+    }
+}
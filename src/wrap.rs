@@ -0,0 +1,125 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::indent::char_width;
+
+/// A [`fmt::Write`] adapter that wraps the text written through it at the
+/// given width.
+///
+/// Lines are only broken at spaces, so words longer than the width are kept
+/// intact. ANSI escape sequences are not taken into account when calculating
+/// the width. The text is buffered word by word, so [`Wrapped::finish`] must
+/// be called once the whole text is written.
+pub(crate) struct Wrapped<'a, W: ?Sized> {
+    inner: &'a mut W,
+    width: usize,
+    column: usize,
+    /// Spaces which precede the current word.
+    spaces: usize,
+    word: String,
+    word_width: usize,
+    escape: bool,
+}
+
+impl<'a, W> Wrapped<'a, W>
+where
+    W: Write + ?Sized,
+{
+    /// Wraps the writer, breaking the lines longer than `width`.
+    pub(crate) fn new(inner: &'a mut W, width: usize) -> Self {
+        Wrapped {
+            inner,
+            width,
+            column: 0,
+            spaces: 0,
+            word: String::new(),
+            word_width: 0,
+            escape: false,
+        }
+    }
+
+    /// Writes out the buffered text.
+    pub(crate) fn finish(mut self) -> fmt::Result {
+        self.flush_word()?;
+        write!(self.inner, "{:width$}", "", width = self.spaces)
+    }
+
+    /// Writes out the current word along with the preceding spaces, or
+    /// starts a new line if the word doesn't fit into the current one.
+    fn flush_word(&mut self) -> fmt::Result {
+        if self.word.is_empty() {
+            return Ok(());
+        }
+        if self.column != 0 && self.column + self.spaces + self.word_width > self.width {
+            self.inner.write_char('\n')?;
+            self.column = 0;
+        } else {
+            write!(self.inner, "{:width$}", "", width = self.spaces)?;
+            self.column += self.spaces;
+        }
+        self.inner.write_str(&self.word)?;
+        self.column += self.word_width;
+        self.spaces = 0;
+        self.word.clear();
+        self.word_width = 0;
+        Ok(())
+    }
+}
+
+impl<W> Write for Wrapped<'_, W>
+where
+    W: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.escape {
+                self.escape = !c.is_ascii_alphabetic();
+                self.word.push(c);
+            } else if c == '\x1b' {
+                self.escape = true;
+                self.word.push(c);
+            } else if c == '\n' {
+                self.flush_word()?;
+                self.inner.write_char('\n')?;
+                self.column = 0;
+                self.spaces = 0;
+            } else if c == ' ' {
+                self.flush_word()?;
+                self.spaces += 1;
+            } else {
+                self.word.push(c);
+                self.word_width += char_width(c);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Wrapped;
+    use std::fmt::Write as _;
+
+    #[test]
+    fn wraps_at_spaces() {
+        let mut output = String::new();
+        let mut wrapped = Wrapped::new(&mut output, 10);
+        write!(wrapped, "one two three fo").unwrap();
+        write!(wrapped, "ur\x1b[1mfive\x1b[0m\nsix seven eightnineten").unwrap();
+        wrapped.finish().unwrap();
+        assert_eq!(
+            output,
+            "one two\nthree\nfour\x1b[1mfive\x1b[0m\nsix seven\neightnineten"
+        );
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn wide_characters() {
+        let mut output = String::new();
+        let mut wrapped = Wrapped::new(&mut output, 10);
+        write!(wrapped, "エラー エラー ab").unwrap();
+        wrapped.finish().unwrap();
+        assert_eq!(output, "エラー\nエラー ab");
+    }
+}